@@ -1,16 +1,24 @@
+import os
+import re
+from contextlib import closing
+from itertools import chain
+
 import jinja2
 
-import glad
-from glad.config import Config, ConfigOption
+import glad.files
+from glad.config import Config, ConfigOption, RequirementConstraint, UnsupportedConstraint
 from glad.generator import JinjaGenerator
+from glad.generator.c import CGenerator
 from glad.generator.util import (
+    is_device_command,
     strip_specification_prefix,
     collect_alias_information,
     find_extensions_with_aliases,
     jinja2_contextfilter
 )
-from glad.parse import ParsedType, EnumType
+from glad.parse import ParsedType, EnumType, BitmaskType
 from glad.sink import LoggingSink
+from glad.specification import GL
 
 
 _RUST_TYPE_MAPPING = {
@@ -26,101 +34,459 @@ _RUST_TYPE_MAPPING = {
     'int16_t': 'i16',
     'uint16_t': 'u16',
     'int32_t': 'i32',
-    'int64_t': 'i32',
+    'int64_t': 'i64',
     'uint32_t': 'u32',
     'uint64_t': 'u64',
     'size_t': 'usize',
+    'u': 'c_uint',
     'ull': 'u64',
 }
 
 
-def enum_type(enum, feature_set):
-    if enum.alias and enum.value is None:
-        aliased = feature_set.find_enum(enum.alias)
-        if aliased is None:
-            raise ValueError('unable to resolve enum alias {} of enum {}'.format(enum.alias, enum))
-        enum = aliased
+class ExpressionError(ValueError):
+    pass
+
+
+class CValue(object):
+    """
+    Result of a C constant expression:
+
+    - `value`: the Python value, `int`, `float` or `str`
+    - `bits` and `signed`: the C integer type of integers, `bits` is `None` for other values
+    - `cast`: name of the type the value was cast to, if the type is not known
+    - `literal`: source of the value if it is a single literal, used to render it as written
+    """
+    def __init__(self, value, bits=None, signed=True, cast=None, literal=None):
+        self.value = value
+        self.bits = bits
+        self.signed = signed
+        self.cast = cast
+        self.literal = literal
+
+    @property
+    def is_integer(self):
+        return self.bits is not None
+
+
+# integer types as (bits, signed), long is assumed to be 64 bits wide
+_C_INTEGER_TYPES = {
+    'u': (32, False),
+    'ull': (64, False),
+    'char': (8, True),
+    'int': (32, True),
+    'long': (64, True),
+    'int8_t': (8, True),
+    'uint8_t': (8, False),
+    'int16_t': (16, True),
+    'uint16_t': (16, False),
+    'int32_t': (32, True),
+    'uint32_t': (32, False),
+    'int64_t': (64, True),
+    'uint64_t': (64, False),
+    'size_t': (64, False),
+}
+
+_RUST_INTEGER_TYPES = {
+    (8, True): 'i8', (8, False): 'u8',
+    (16, True): 'i16', (16, False): 'u16',
+    (32, True): 'c_int', (32, False): 'c_uint',
+    (64, True): 'i64', (64, False): 'u64',
+}
+
+_TOKEN_RE = re.compile(r'''\s*(?:
+    (?P<float>(?:\d+\.\d*|\.\d+)(?:[eE][-+]?\d+)?[fF]?|\d+[eE][-+]?\d+[fF]?)
+  | (?P<int>(?:0[xX][0-9a-fA-F]+|\d+)[uUlL]*)
+  | (?P<string>"(?:[^"\\]|\\.)*")
+  | (?P<name>[A-Za-z_]\w*)
+  | (?P<op><<|>>|[-+~!*/%&|^(),])
+)''', re.VERBOSE)
+
+# binary operators by precedence, lowest first
+_BINARY_OPERATORS = [('|',), ('^',), ('&',), ('<<', '>>'), ('+', '-'), ('*', '/', '%')]
+
+# function-like macros casting their second argument to the type given as first argument
+_CAST_MACROS = ('EGL_CAST',)
+
+
+def _wrap(value, bits, signed):
+    value &= (1 << bits) - 1
+    if signed and value >= 1 << (bits - 1):
+        value -= 1 << bits
+    return value
+
+
+def _fits(value, bits, signed):
+    if signed:
+        return -(1 << (bits - 1)) <= value < 1 << (bits - 1)
+    return 0 <= value < 1 << bits
+
+
+def _integer_literal(token):
+    digits = token.rstrip('uUlL')
+    suffix = token[len(digits):].lower()
+    is_decimal = digits[0] != '0' or digits == '0'
+    value = int(digits, 10 if is_decimal else 16 if digits[:2] in ('0x', '0X') else 8)
+
+    # the first type the value fits in, like the C standard does
+    candidates = [(32, True), (32, False), (64, True), (64, False)]
+    if 'u' in suffix:
+        candidates = [c for c in candidates if not c[1]]
+    elif is_decimal:
+        # decimal literals without suffix are never unsigned
+        candidates = [c for c in candidates if c[1]]
+    if 'l' in suffix:
+        candidates = [c for c in candidates if c[0] == 64]
+
+    # octal literals are written differently in Rust
+    literal = digits if is_decimal or digits[:2] in ('0x', '0X') else None
+    for bits, signed in candidates:
+        if _fits(value, bits, signed):
+            return CValue(value, bits, signed, literal=literal)
+
+    raise ExpressionError('integer literal {} is too large'.format(token))
 
-    # if the value links to another enum, resolve the enum right now
-    if enum.value is not None:
-        # enum = feature_set.find_enum(enum.value, default=enum)
-        referenced = feature_set.find_enum(enum.value)
-        # TODO currently every enum with a parent type is u32
-        if referenced is not None and referenced.parent_type is not None:
-            return 'u32'
 
-    # we could return GLenum and friends here
-    # but thanks to type aliasing we don't have to
-    # this makes handling types for different specifications
-    # easier, since we don't have to swap types. GLenum -> XXenum.
-    if enum.type:
-        _RUST_TYPE_MAPPING.get(enum.type, 'c_uint')
+class _Parser(object):
+    """
+    Recursive descent parser evaluating C constant expressions.
 
-    if enum.value.startswith('0x'):
-        return 'u64' if len(enum.value[2:]) > 8 else 'c_uint'
+    Identifiers are resolved through `resolve`, which evaluates the referenced enum.
+    """
+    def __init__(self, expression, resolve):
+        self.resolve = resolve
+        self.tokens = self._tokenize(expression)
+        self.position = 0
 
-    if enum.name in ('GL_TRUE', 'GL_FALSE'):
-        return 'c_uchar'
+    @staticmethod
+    def _tokenize(expression):
+        tokens = []
+        position = 0
+        expression = expression.rstrip()
+        while position < len(expression):
+            match = _TOKEN_RE.match(expression, position)
+            if match is None or match.end() == position:
+                raise ExpressionError('unexpected character {!r}'.format(expression[position:].strip()[:1]))
+            tokens.append((match.lastgroup, match.group(match.lastgroup)))
+            position = match.end()
+        return tokens
 
-    if enum.value.startswith('-'):
-        return 'c_int'
+    def peek(self, offset=0):
+        index = self.position + offset
+        return self.tokens[index] if index < len(self.tokens) else (None, None)
 
-    if enum.value.endswith('f') or enum.value.endswith('F'):
-        return 'c_float'
+    def next(self):
+        token = self.peek()
+        if token[0] is None:
+            raise ExpressionError('unexpected end of expression')
+        self.position += 1
+        return token
 
-    if enum.value.startswith('"'):
-        # TODO figure out correct type
-        return '&str'
+    def expect(self, op):
+        kind, value = self.next()
+        if kind != 'op' or value != op:
+            raise ExpressionError('expected {!r} but found {!r}'.format(op, value))
 
-    if enum.value.startswith('(('):
-        # Casts: '((Type)value)' -> 'Type'
-        raise NotImplementedError
+    def parse(self):
+        result = self.binary(0)
+        if self.peek()[0] is not None:
+            raise ExpressionError('unexpected {!r}'.format(self.peek()[1]))
+        return result
 
-    if enum.value.startswith('EGL_CAST'):
-        # EGL_CAST(type,value) -> type
-        return enum.value.split('(', 1)[1].split(',')[0]
+    def binary(self, level):
+        if level == len(_BINARY_OPERATORS):
+            return self.unary()
 
-    return 'c_uint'
+        left = self.binary(level + 1)
+        while self.peek()[0] == 'op' and self.peek()[1] in _BINARY_OPERATORS[level]:
+            op = self.next()[1]
+            left = _binary(op, left, self.binary(level + 1))
+        return left
 
+    def unary(self):
+        kind, value = self.peek()
+        if kind == 'op' and value in ('-', '+', '~', '!'):
+            self.next()
+            operand = self.unary()
+            return _unary(value, operand)
+        if kind == 'op' and value == '(' and self.peek(1)[0] == 'name' and \
+                self.peek(2) == ('op', ')') and self.resolve(self.peek(1)[1], required=False) is None:
+            self.next()
+            type_ = self.next()[1]
+            self.next()
+            return _cast(type_, self.unary())
+        return self.primary()
 
-def enum_value(enum, feature_set):
-    if enum.alias and enum.value is None:
-        enum = feature_set.find_enum(enum.alias)
+    def primary(self):
+        kind, value = self.next()
+        if kind == 'int':
+            return _integer_literal(value)
+        if kind == 'float':
+            literal = value.rstrip('fF')
+            return CValue(float(literal), cast='float' if literal != value else None, literal=literal)
+        if kind == 'string':
+            return CValue(value[1:-1])
+        if kind == 'name' and value in _CAST_MACROS:
+            self.expect('(')
+            type_ = self.next()
+            if type_[0] != 'name':
+                raise ExpressionError('expected a type but found {!r}'.format(type_[1]))
+            self.expect(',')
+            operand = self.binary(0)
+            self.expect(')')
+            return _cast(type_[1], operand)
+        if kind == 'name':
+            return self.resolve(value)
+        if kind == 'op' and value == '(':
+            result = self.binary(0)
+            self.expect(')')
+            return result
+        raise ExpressionError('unexpected {!r}'.format(value))
 
-    # basically an alias to another enum (value contains another enum)
-    # resolve it here and adjust value accordingly.
-    referenced = feature_set.find_enum(enum.value)
-    if referenced is None:
-        pass
-    elif referenced.parent_type is not None:
-        # global value is a reference to a enum type value
-        return '{}::{} as u32'.format(referenced.parent_type, enum.value)
+
+def _require_integer(op, *operands):
+    for operand in operands:
+        if not operand.is_integer:
+            raise ExpressionError('operator {!r} requires integer operands'.format(op))
+
+
+def _unary(op, operand):
+    if op == '+':
+        return operand
+    if op == '-' and not operand.is_integer:
+        if not isinstance(operand.value, float):
+            raise ExpressionError('operator {!r} requires a number'.format(op))
+        literal = '-' + operand.literal if operand.literal else None
+        return CValue(-operand.value, cast=operand.cast, literal=literal)
+
+    _require_integer(op, operand)
+    if op == '!':
+        return CValue(int(not operand.value), 32, True)
+
+    bits, signed = max(operand.bits, 32), operand.signed or operand.bits < 32
+    if op == '-':
+        literal = '-' + operand.literal if operand.literal and operand.value != 0 else None
+        return CValue(_wrap(-operand.value, bits, signed), bits, signed, operand.cast, literal)
+    return CValue(_wrap(~operand.value, bits, signed), bits, signed, operand.cast)
+
+
+def _binary(op, left, right):
+    if op in ('+', '-', '*', '/') and not (left.is_integer and right.is_integer):
+        if not all(isinstance(v.value, (int, float)) for v in (left, right)):
+            raise ExpressionError('operator {!r} requires numbers'.format(op))
+        if op == '/' and right.value == 0:
+            raise ExpressionError('division by zero')
+        value = {'+': lambda a, b: a + b, '-': lambda a, b: a - b,
+                 '*': lambda a, b: a * b, '/': lambda a, b: a / b}[op](float(left.value), float(right.value))
+        is_float = all(v.is_integer or v.cast == 'float' for v in (left, right))
+        return CValue(value, cast='float' if is_float else None)
+
+    _require_integer(op, left, right)
+    if op in ('<<', '>>'):
+        # the result has the promoted type of the left operand
+        bits, signed = max(left.bits, 32), left.signed or left.bits < 32
+        if not 0 <= right.value < bits:
+            raise ExpressionError('shift by {} is out of range'.format(right.value))
+    else:
+        # usual arithmetic conversions
+        (lbits, lsigned), (rbits, rsigned) = [(max(v.bits, 32), v.signed or v.bits < 32) for v in (left, right)]
+        bits = max(lbits, rbits)
+        if lbits == rbits:
+            signed = lsigned and rsigned
+        else:
+            signed = lsigned if lbits > rbits else rsigned
+
+    a, b = left.value, right.value
+    if op in ('/', '%'):
+        if b == 0:
+            raise ExpressionError('division by zero')
+        # C truncates towards zero
+        quotient = abs(a) // abs(b) * (1 if (a < 0) == (b < 0) else -1)
+        value = quotient if op == '/' else a - quotient * b
     else:
-        enum = referenced
+        value = {
+            '|': lambda: a | b, '^': lambda: a ^ b, '&': lambda: a & b,
+            '<<': lambda: a << b, '>>': lambda: a >> b,
+            '+': lambda: a + b, '-': lambda: a - b, '*': lambda: a * b
+        }[op]()
+    return CValue(_wrap(value, bits, signed), bits, signed)
 
-    value = enum.value
-    if value.endswith('"'):
-        value = value[:-1] + r'\0"'
-        return value
 
-    if enum.value.startswith('EGL_CAST'):
-        # EGL_CAST(type,value) -> value as type
-        type_, value = enum.value.split('(', 1)[1].rsplit(')', 1)[0].split(',')
-        return '{} as {}'.format(value, type_)
+def _cast(type_, operand):
+    if type_ in ('float', 'double'):
+        if not isinstance(operand.value, (int, float)):
+            raise ExpressionError('can not cast {!r} to {}'.format(operand.value, type_))
+        return CValue(float(operand.value), cast=type_ if type_ == 'float' else None, literal=operand.literal)
 
-    if enum.type == 'float' and value.endswith('F'):
-        value = value[:-1]
+    if not isinstance(operand.value, int):
+        raise ExpressionError('can not cast {!r} to {}'.format(operand.value, type_))
 
-    for old, new in (('(', ''), (')', ''), ('f', ''),
-                     ('U', ''), ('L', ''), ('~', '!')):
-        value = value.replace(old, new)
+    if type_ in _C_INTEGER_TYPES:
+        bits, signed = _C_INTEGER_TYPES[type_]
+        value = _wrap(operand.value, bits, signed)
+        return CValue(value, bits, signed, literal=operand.literal if value == operand.value else None)
 
-    return value
+    # typedefs of the specification, converted by Rust
+    return CValue(operand.value, operand.bits, operand.signed, cast=type_, literal=operand.literal)
+
+
+def evaluate_expression(expression, resolve):
+    """
+    Evaluates a C constant expression as used for enum values in the specifications.
+
+    Supported are integer, floating point and string literals with their suffixes,
+    unary and binary arithmetic and bitwise operators, casts and `EGL_CAST`.
+
+    :param expression: the expression
+    :param resolve: function taking an identifier and a keyword `required`,
+                    returns the CValue of the referenced enum or `None` if it is not required
+    :return: CValue
+    :raises ExpressionError: if the expression is not supported
+    """
+    return _Parser(expression, resolve).parse()
+
+
+class Constant(object):
+    """
+    Enum as Rust constant with its `type` and `value`.
+    """
+    def __init__(self, name, type_, value):
+        self.name = name
+        self.type = type_
+        self.value = value
+
+
+def to_rust_constant(enum, result, type_=None):
+    """
+    Renders an evaluated enum as Rust constant.
+
+    :param enum: the enum
+    :param result: the CValue of its value
+    :param type_: the type specified on the enum, overrides the type of the expression
+    :return: Constant
+    """
+    if isinstance(result.value, str):
+        return Constant(enum.name, '&str', '"{}\\0"'.format(result.value))
+
+    if isinstance(result.value, float):
+        rust_type = _RUST_TYPE_MAPPING.get(type_, 'c_float' if result.cast == 'float' else 'c_double')
+        if rust_type not in ('c_float', 'c_double'):
+            raise ExpressionError('floating point value for type {}'.format(type_))
+        literal = result.literal or repr(result.value)
+        if '.' not in literal and 'e' not in literal.lower():
+            literal += '.0'
+        return Constant(enum.name, rust_type, literal)
+
+    if result.cast is not None and type_ is None:
+        return Constant(enum.name, result.cast, '{} as {}'.format(result.literal or result.value, result.cast))
 
+    if type_ in ('float', 'double'):
+        return to_rust_constant(enum, _cast(type_, result), type_)
 
-def to_rust_type(type_):
+    if type_ is not None:
+        if type_ not in _C_INTEGER_TYPES:
+            raise ExpressionError('unknown type {}'.format(type_))
+        bits, signed = _C_INTEGER_TYPES[type_]
+        rust_type = _RUST_TYPE_MAPPING[type_]
+    elif enum.name in ('GL_TRUE', 'GL_FALSE'):
+        bits, signed, rust_type = 8, False, 'c_uchar'
+    elif result.value < 0:
+        # negative values are plain ints, unless they do not fit
+        bits, signed = max(result.bits, 32), True
+        rust_type = _RUST_INTEGER_TYPES[(bits, signed)]
+    else:
+        # we could return GLenum and friends here
+        # but thanks to type aliasing we don't have to
+        bits = 32 if result.bits == 32 or result.value < 1 << 32 else 64
+        signed = False
+        rust_type = _RUST_INTEGER_TYPES[(bits, signed)]
+
+    value = result.value
+    if not signed and value < 0:
+        # conversion to unsigned, like `(unsigned int)-1`
+        value = _wrap(value, bits, signed)
+    if not _fits(value, bits, signed):
+        raise ExpressionError('value {} does not fit into {}'.format(result.value, rust_type))
+
+    if result.literal is not None and value == result.value:
+        literal = result.literal
+    elif value < 0:
+        literal = str(value)
+    else:
+        literal = '0x{:X}'.format(value)
+    return Constant(enum.name, rust_type, literal)
+
+
+def collect_constants(feature_set, sink=LoggingSink(__name__)):
+    """
+    Evaluates the values of all enums of the feature set.
+
+    Enums with an expression which can not be evaluated are reported
+    to the sink and omitted.
+
+    :param feature_set: the feature set
+    :param sink: sink the unsupported expressions are reported to
+    :return: dictionary of Constant by enum name
+    """
+    results = dict()
+    resolving = set()
+
+    def resolve(name, required=True):
+        enum = feature_set.find_enum(name)
+        if enum is None:
+            if required:
+                raise ExpressionError('unknown identifier {}'.format(name))
+            return None
+        return evaluate(enum)
+
+    def evaluate(enum):
+        if enum.name in results:
+            return results[enum.name]
+        if enum.name in resolving:
+            raise ExpressionError('{} references itself'.format(enum.name))
+
+        resolving.add(enum.name)
+        try:
+            if enum.value is None and enum.alias is not None:
+                result = resolve(enum.alias)
+            elif enum.value is None:
+                raise ExpressionError('{} has no value'.format(enum.name))
+            else:
+                result = evaluate_expression(enum.value, resolve)
+                if enum.parent_type is not None and result.is_integer:
+                    # members of enum types are 32 bit integers
+                    result = _cast('int32_t', result)
+        finally:
+            resolving.discard(enum.name)
+
+        results[enum.name] = result
+        return result
+
+    constants = dict()
+    for enum in feature_set.enums:
+        try:
+            result = evaluate(enum)
+            if enum.value is None and enum.alias is not None:
+                type_ = getattr(feature_set.find_enum(enum.alias), 'type', None)
+            else:
+                type_ = enum.type
+            constants[enum.name] = to_rust_constant(enum, result, type_)
+        except ExpressionError as e:
+            sink.warning('unable to evaluate value {!r} of enum {}: {}'.format(enum.value, enum.name, e))
+
+    return constants
+
+
+def to_rust_type(type_, spec=None):
+    """
+    Rust type of a C type, array lengths which reference a constant
+    refer to the constant of the enumerations module.
+
+    :param type_: the ParsedType or a C type
+    :param spec: the specification, used to name the constants
+    :return: the Rust type
+    """
     if type_ is None:
-        return 'std::os::raw::c_void'
+        return 'core::ffi::c_void'
 
     parsed_type = type_ if isinstance(type_, ParsedType) else ParsedType.from_string(type_)
 
@@ -136,26 +502,1263 @@ def to_rust_type(type_):
 
     type_ = _RUST_TYPE_MAPPING.get(parsed_type.type, parsed_type.type)
 
-    if parsed_type.is_array > 0:
-        type_ = '[{};{}]'.format(type_, parsed_type.is_array)
+    for dimension in reversed(parsed_type.array_dimensions):
+        if not isinstance(dimension, int):
+            dimension = 'super::enumerations::{} as usize'.format(strip_specification_prefix(dimension, spec))
+        type_ = '[{};{}]'.format(type_, dimension)
 
     return ' '.join(e.strip() for e in (prefix, type_)).strip()
 
 
-def to_rust_params(command, mode='full'):
+def to_param_type(param, groups=None, group_path=''):
+    """
+    Type of a parameter, enum parameters which belong to a generated group
+    are typed with the group instead.
+
+    :param param: the parameter
+    :param groups: dictionary of generated enum groups by name
+    :param group_path: path the group types are prefixed with
+    :return: ParsedType of the parameter
+    """
+    type_ = param.type
+    group = (groups or dict()).get(param.group)
+    if group is None or type_.type != group.type:
+        return type_
+
+    return ParsedType(
+        type_.name, group_path + group.name, group.name, is_pointer=type_.is_pointer,
+        is_array=type_.is_array, is_const=type_.is_const, raw=type_._raw,
+        array_dimensions=type_.array_dimensions
+    )
+
+
+def to_rust_params(command, mode='full', groups=None, group_path=''):
     if mode == 'names':
         return ', '.join(identifier(param.name) for param in command.params)
     elif mode == 'types':
-        return ', '.join(to_rust_type(param.type) for param in command.params)
+        return ', '.join(to_rust_type(to_param_type(param, groups, group_path)) for param in command.params)
     elif mode == 'full':
         return ', '.join(
-            '{name}: {type}'.format(name=identifier(param.name), type=to_rust_type(param.type))
+            '{name}: {type}'.format(
+                name=identifier(param.name), type=to_rust_type(to_param_type(param, groups, group_path))
+            )
             for param in command.params
         )
 
     raise ValueError('invalid mode: ' + mode)
 
 
+class EnumGroup(object):
+    def __init__(self, name, type_):
+        self.name = name
+        # underlying type, GLenum or GLbitfield
+        self.type = type_
+        self.enums = []
+
+    @property
+    def is_bitmask(self):
+        return self.type == 'GLbitfield'
+
+
+def collect_enum_groups(feature_set, constants):
+    """
+    Collects the enum groups used by parameters of the commands.
+
+    Only groups of `GLenum` and `GLbitfield` parameters are collected,
+    if a group is used with both types, the first one wins.
+
+    :param feature_set: the feature set
+    :param constants: dictionary of Constant by enum name
+    :return: dictionary of EnumGroup by name
+    """
+    groups = dict()
+    for command in feature_set.commands:
+        for param in command.params:
+            if param.group and param.group not in groups and param.type.type in ('GLenum', 'GLbitfield'):
+                groups[param.group] = EnumGroup(param.group, param.type.type)
+
+    for enum in feature_set.enums:
+        # values wider than the group type do not fit
+        constant = constants.get(enum.name)
+        if constant is None or constant.type == 'u64':
+            continue
+        for name in enum.groups:
+            if name in groups:
+                groups[name].enums.append(enum)
+
+    return groups
+
+
+def collect_bitmask_information(types):
+    """
+    Pairs bitmask types with the enum type containing their bits.
+
+    :param types: types of the feature set
+    :return: a dictionary mapping bitmask names to their enum type (or None)
+             and a dictionary mapping enum names to their bitmask name
+    """
+    enum_types = dict((t.name, t) for t in types if isinstance(t, EnumType))
+
+    bits = dict()
+    for typ in types:
+        if isinstance(typ, BitmaskType) and typ.alias is None:
+            bits[typ.name] = enum_types.get(typ.bitvalues)
+
+    bitmasks = dict((enum.name, name) for name, enum in bits.items() if enum is not None)
+    return bits, bitmasks
+
+
+def zeroable_checker(types, newtype_enums=False):
+    """
+    Creates a function which checks if a type is valid when zero initialized.
+
+    :param types: types of the feature set
+    :param newtype_enums: enums are generated as newtypes, which can hold any value
+    :return: function taking a type name, returning a boolean
+    """
+    types_by_name = dict((t.name, t) for t in types)
+    zeroable = dict()
+
+    def is_zero_value(value):
+        try:
+            return int(value, 0) == 0
+        except (TypeError, ValueError):
+            return False
+
+    def is_zeroable(name):
+        if name in zeroable:
+            return zeroable[name]
+        # assume recursive types are fine, they can only recurse through pointers
+        zeroable[name] = True
+
+        typ = types_by_name.get(name)
+        if typ is None:
+            # builtin or platform type
+            result = True
+        elif typ.alias is not None:
+            result = is_zeroable(typ.alias)
+        elif typ.category == 'enum':
+            result = typ.is_bitmask or newtype_enums or any(is_zero_value(e.value) for e in typ.enums if not e.alias)
+        elif typ.category in ('struct', 'union'):
+            result = all(m.type.is_pointer or is_zeroable(m.type.type) for m in typ.members)
+        else:
+            result = True
+
+        zeroable[name] = result
+        return result
+
+    return is_zeroable
+
+
+def collect_struct_information(types, newtype_enums=False):
+    """
+    Collects which structs and unions are valid when zero initialized
+    and which structs can be chained into other structs through `pNext`.
+
+    :param types: types of the feature set
+    :param newtype_enums: enums are generated as newtypes, which can hold any value
+    :return: a set of struct and union names which can be zero initialized
+             and a dictionary mapping struct names to the structs extending them
+    """
+    types_by_name = dict((t.name, t) for t in types)
+    is_zeroable = zeroable_checker(types, newtype_enums)
+
+    structs = [t for t in types if t.category in ('struct', 'union') and t.alias is None]
+    default_types = set(t.name for t in structs if t.members and is_zeroable(t.name))
+
+    def has_next(typ):
+        return any(m.name == 'pNext' for m in getattr(typ, 'members', []))
+
+    extended_by = dict()
+    for typ in structs:
+        for name in getattr(typ, 'structextends', []):
+            base = types_by_name.get(name)
+            if base is not None and base.alias is None and has_next(base) and has_next(typ):
+                extended_by.setdefault(name, []).append(typ)
+
+    return default_types, extended_by
+
+
+_ALTLEN_RE = re.compile(r'^(\w+)\s*/\s*(\d+)$')
+
+
+class BuilderSetter(object):
+    """
+    Setter of a struct builder, `assignments` are pairs of a field and the expression assigned to it.
+    """
+    def __init__(self, name, type_, assignments):
+        self.name = name
+        self.type = type_
+        self.assignments = assignments
+
+
+def to_builder_setters(typ, spec=None):
+    """
+    Creates the setters of the builder of a struct.
+
+    Pointers to constant data take references which are bound to the lifetime of the builder,
+    pointers counted by another member take slices which also set the count.
+    Other pointers are set as raw pointers.
+
+    :param typ: the struct
+    :param spec: the specification, used to name constants
+    :return: list of BuilderSetter
+    """
+    members = dict((member.name, member) for member in typ.members)
+
+    setters = []
+    for member in typ.members:
+        if member.values or member.name == 'pNext' or member.type.bitfield is not None:
+            continue
+
+        name = identifier(member.name)
+        type_ = member.type
+        lengths = (member.len or '').split(',')
+        count = members.get(lengths[0])
+        factor = 1
+        match = _ALTLEN_RE.match(member.altlen or '')
+        if match is not None:
+            # the count is in bytes, e.g. `codeSize / 4`
+            count, factor = members.get(match.group(1)), int(match.group(2))
+
+        if not type_.is_pointer or not type_.is_const or type_.is_array:
+            setters.append(BuilderSetter(name, to_rust_type(type_, spec), [(name, name)]))
+        elif count is not None and not count.type.is_pointer:
+            if type_.is_pointer == 1 and type_.type == 'void':
+                # untyped data is counted in bytes
+                element, pointer = 'u8', '{}.as_ptr() as *const c_void'.format(name)
+            else:
+                element = to_rust_type(ParsedType(
+                    None, type_.type, type_.original_type, is_pointer=type_.is_pointer - 1, is_const=True
+                ), spec)
+                pointer = '{}.as_ptr()'.format(name)
+            length = '{}.len()'.format(name) if factor == 1 else '({}.len() * {})'.format(name, factor)
+            setters.append(BuilderSetter(name, "&'a [{}]".format(element), [
+                (name, pointer),
+                (identifier(count.name), '{} as {}'.format(length, to_rust_type(count.type, spec)))
+            ]))
+        elif type_.is_pointer == 1 and type_.type in _CHAR_TYPES and lengths[0] == 'null-terminated':
+            setters.append(BuilderSetter(name, "&'a CStr", [(name, '{}.as_ptr()'.format(name))]))
+        elif type_.is_pointer == 1 and type_.type != 'void' and member.len is None:
+            element = to_rust_type(ParsedType(None, type_.type, type_.original_type), spec)
+            setters.append(BuilderSetter(name, "&'a {}".format(element), [(name, name)]))
+        else:
+            setters.append(BuilderSetter(name, to_rust_type(type_, spec), [(name, name)]))
+
+    return setters
+
+
+class Bitfield(object):
+    """
+    Bitfield member of a struct, stored in `width` bits at `offset` of its unit,
+    `flags` is the integer type of bitmask members.
+    """
+    def __init__(self, member, offset, width, flags=None):
+        self.name = identifier(member.name)
+        self.type = member.type
+        self.offset = offset
+        self.width = width
+        self.mask = hex((1 << width) - 1)
+        self.flags = flags
+
+
+class BitfieldUnit(object):
+    """
+    Storage unit of consecutive bitfields, named after its bitfields.
+    """
+    def __init__(self, bits):
+        self.bits = bits
+        self.type = 'u{}'.format(bits)
+        self.fields = []
+
+    @property
+    def name(self):
+        return '_and_'.join(field.name for field in self.fields)
+
+    @property
+    def used(self):
+        return sum(field.width for field in self.fields)
+
+
+def collect_bitfields(types):
+    """
+    Packs the bitfield members of structs into storage units the way C compilers do,
+    consecutive bitfields share a unit as long as their type has the same size
+    and they fit into it.
+
+    :param types: types of the feature set
+    :return: a dictionary mapping struct names to a dictionary mapping
+             the name of the first member of a unit to the unit
+    """
+    types_by_name = dict((t.name, t) for t in types)
+
+    def integer_type(name):
+        flags = None
+        while name not in _C_INTEGER_TYPES:
+            typ = types_by_name[name]
+            if typ.category == 'bitmask' and flags is None:
+                flags = typ.type
+            name = typ.alias or typ.type
+        return _C_INTEGER_TYPES[name][0], flags
+
+    bitfields = dict()
+    for typ in types:
+        if typ.category not in ('struct', 'union'):
+            continue
+
+        units = dict()
+        unit = None
+        for member in getattr(typ, 'members', []):
+            width = member.type.bitfield
+            if width is None:
+                unit = None
+                continue
+
+            bits, flags = integer_type(member.type.type)
+            if unit is None or unit.bits != bits or unit.used + width > bits:
+                unit = BitfieldUnit(bits)
+                units[member.name] = unit
+            unit.fields.append(Bitfield(member, unit.used, width, flags))
+
+        if units:
+            bitfields[typ.name] = units
+
+    return bitfields
+
+
+def collect_feature_gates(spec, feature_set):
+    """
+    Collects the cargo features which enable a command or enum,
+    a symbol is available if any of its features is enabled.
+
+    Enums used as array lengths by the types of the feature set are not gated,
+    the types are available independent of the features.
+
+    :param spec: the specification
+    :param feature_set: the feature set
+    :return: a dictionary mapping command and enum names to a list of feature names
+    """
+    lengths = set(
+        dimension for typ in feature_set.types for member in getattr(typ, 'members', None) or []
+        for dimension in member.type.array_dimensions if not isinstance(dimension, int)
+    )
+
+    gates = dict()
+    for feature in chain(feature_set.features, feature_set.extensions):
+        requirements = feature.get_requirements(spec, feature_set=feature_set)
+        for symbol in chain(requirements.enums, requirements.commands):
+            if symbol.name not in lengths:
+                gates.setdefault(symbol.name, []).append(feature.name)
+
+    return gates
+
+
+def _split_dependencies(depends, separator):
+    """
+    Splits a dependency expression at the separators outside of parentheses.
+    """
+    terms = list()
+    depth = 0
+    term = ''
+    for c in depends:
+        if c == separator and depth == 0:
+            terms.append(term.strip())
+            term = ''
+            continue
+        depth += {'(': 1, ')': -1}.get(c, 0)
+        term += c
+
+    terms.append(term.strip())
+    return terms
+
+
+def _required_dependencies(depends, names):
+    """
+    Extracts the dependencies of a dependency expression which are enabled
+    together with an extension. Alternatives (`,`) can not be expressed as
+    cargo features, the first alternative whose dependencies are all
+    part of `names` is picked.
+
+    E.g. `A+(B,C)+(D+E)` requires `A`, `B`, `D` and `E`.
+    """
+    required = list()
+    for term in _split_dependencies(depends or '', '+'):
+        alternatives = _split_dependencies(term, ',')
+        if len(alternatives) > 1:
+            candidates = (_required_dependencies(alternative, names) for alternative in alternatives)
+            required.extend(next((c for c in candidates if all(d in names for d in c)), []))
+        elif term.startswith('(') and term.endswith(')'):
+            required.extend(_required_dependencies(term[1:-1], names))
+        elif re.match(r'^\w+$', term):
+            required.append(term)
+
+    return required
+
+
+def collect_cargo_features(feature_set):
+    """
+    Creates a cargo feature for every version and extension of the feature set,
+    versions imply the previous version of the same API, extensions imply their dependencies,
+    all of them are enabled by default.
+
+    :param feature_set: the feature set
+    :return: list of tuples of the feature name and the implied feature names
+    """
+    names = set(f.name for f in chain(feature_set.features, feature_set.extensions))
+
+    features = list()
+    previous = dict()
+    for feature in sorted(feature_set.features, key=lambda f: f.version):
+        api = tuple(sorted(feature.api))
+        features.append((feature.name, [previous[api]] if api in previous else []))
+        previous[api] = feature.name
+
+    for extension in feature_set.extensions:
+        dependencies = _required_dependencies(extension.depends, names)
+        features.append((extension.name, [d for d in dependencies if d in names]))
+
+    return features
+
+
+def collect_dispatch_information(commands):
+    """
+    Splits Vulkan commands by the level they are dispatched on.
+
+    :param commands: commands of the feature set
+    :return: a dictionary with the global (entry), instance and device commands
+    """
+    result = dict(entry=[], instance=[], device=[])
+
+    for command in commands:
+        handle = command.params[0].type.type if command.params else None
+
+        if is_device_command(command):
+            result['device'].append(command)
+        elif handle in ('VkInstance', 'VkPhysicalDevice'):
+            result['instance'].append(command)
+        else:
+            result['entry'].append(command)
+
+        # required to load the next level
+        if command.name == 'vkGetInstanceProcAddr':
+            result['entry'].append(command)
+        elif command.name == 'vkGetDeviceProcAddr':
+            result['instance'].append(command)
+
+    return result
+
+
+# specifications with a `load_from_system` loader
+_LOADER_SPECS = ('egl', 'gl', 'vk')
+_CHAR_TYPES = ('char', 'GLchar', 'GLcharARB')
+_LEN_FACTOR_RE = re.compile(r'^(\w+)\s*\*\s*(\d+)$')
+# types declared without a definition in the type templates, these can not be returned
+_OPAQUE_TYPES = (
+    'Display', 'Visual', '_cl_context', '_cl_event',
+    'xcb_connection_t', 'wl_display', 'wl_surface', 'MirConnection', 'MirSurface'
+)
+
+
+class SafeCommand(object):
+    """
+    Description of a wrapper around a command, which takes and returns
+    Rust types instead of raw pointers.
+    """
+    def __init__(self, command):
+        self.command = command
+        self.generics = []
+        # parameters of the wrapper
+        self.params = []
+        # statements before the call
+        self.pre = []
+        # arguments passed to the command
+        self.args = []
+        # arguments of the first call of the two call (count, fill) idiom
+        self.count_args = None
+        # element initializer of the two call idiom (array, count, initializer)
+        self.enumerate = None
+        # statements after the call
+        self.post = []
+        # values returned in addition to the return value of the command
+        self.outs = []
+        self.out_types = []
+
+    @property
+    def ret(self):
+        ret = to_rust_type(self.command.proto.ret)
+        return None if ret == '()' else ret
+
+    @property
+    def return_type(self):
+        types = ([self.ret] if self.ret else []) + self.out_types
+        if not types:
+            return None
+        return types[0] if len(types) == 1 else '({})'.format(', '.join(types))
+
+    @property
+    def return_value(self):
+        values = (['ret'] if self.ret else []) + self.outs
+        if not values:
+            return None
+        return values[0] if len(values) == 1 else '({})'.format(', '.join(values))
+
+
+def to_safe_command(command, types_by_name, is_zeroable, groups=None):
+    """
+    Maps the parameters of a command to Rust types using the `len` and `optional`
+    information of the specification.
+
+    :param command: the command
+    :param types_by_name: dictionary of the types of the feature set by name
+    :param is_zeroable: function checking if a type is valid when zero initialized, see `zeroable_checker`
+    :param groups: dictionary of generated enum groups by name
+    :return: a SafeCommand or None if a parameter can not be mapped
+    """
+
+    def resolve(name):
+        typ = types_by_name.get(name)
+        while typ is not None and typ.alias is not None:
+            typ = types_by_name.get(typ.alias)
+        return typ
+
+    def element_type(type_):
+        if type_.is_pointer == 1 and type_.type == 'void':
+            return None
+        return to_rust_type(ParsedType(
+            None, type_.type, type_.original_type, is_pointer=type_.is_pointer - 1,
+            is_const=type_.is_const, raw=type_._raw
+        ))
+
+    def initializer(type_):
+        if type_.is_pointer > 1:
+            return 'std::ptr::null_mut()' if not type_.is_const else 'std::ptr::null()'
+        typ = resolve(type_.type)
+        if not is_zeroable(type_.type) or (typ is not None and typ.category in ('struct', 'union') and not typ.members):
+            # opaque types can not be created
+            return None
+        if typ is not None and any(m.values for m in getattr(typ, 'members', [])):
+            return '{}::default()'.format(typ.name)
+        return 'std::mem::zeroed()'
+
+    def is_optional(param):
+        return bool(param.optional) and param.optional[0]
+
+    params = dict((p.name, p) for p in command.params)
+
+    # count parameter -> [(array parameter, factor)]
+    counted = dict()
+    enumerate_counts = dict()
+    for param in command.params:
+        if not param.type.is_pointer or not param.len:
+            continue
+        match = _LEN_FACTOR_RE.match(param.len)
+        count, factor = (match.group(1), int(match.group(2))) if match else (param.len, 1)
+        if count not in params:
+            continue
+        if params[count].type.is_pointer:
+            enumerate_counts[count] = param
+        else:
+            counted.setdefault(count, []).append((param, factor))
+
+    def is_char(param):
+        return param.type.type in _CHAR_TYPES
+
+    def is_out_string(param):
+        return is_char(param) and not param.type.is_const and param.type.is_pointer == 1
+
+    # counts which are needed as explicit buffer size remain parameters
+    derived = dict(
+        (count, arrays) for count, arrays in counted.items()
+        if not any(is_out_string(array) for array, _ in arrays)
+    )
+    # string arrays pass their lengths in a separate array
+    string_lengths = dict()
+    for arrays in derived.values():
+        strings = [a for a, _ in arrays if is_char(a) and a.type.is_pointer == 2 and a.type.is_const]
+        lengths = [a for a, _ in arrays if a.name == 'length' and a.type.is_const and a.type.is_pointer == 1]
+        if strings and lengths:
+            string_lengths[lengths[0].name] = strings[0]
+    # output strings use the written length if available
+    length_out = None
+    if any(is_out_string(p) and p.len in counted for p in command.params):
+        length_out = next((p for p in command.params
+                           if p.name == 'length' and p.len == '1' and not p.type.is_const), None)
+
+    safe = SafeCommand(command)
+
+    def length_of(param, factor):
+        name = identifier(param.name)
+        if param.type.type == 'void' and param.type.is_pointer == 1:
+            return 'std::mem::size_of_val({})'.format(name)
+        if factor != 1:
+            return '{}.len() / {}'.format(name, factor)
+        return '{}.len()'.format(name)
+
+    for param in command.params:
+        name = identifier(param.name)
+        type_ = to_param_type(param, groups)
+        element = element_type(type_) if type_.is_pointer else None
+        is_void = type_.is_pointer == 1 and type_.type == 'void'
+
+        if param.name in derived:
+            arrays = derived[param.name]
+            first = length_of(*arrays[0])
+            for array, factor in arrays[1:]:
+                if array.name in string_lengths:
+                    continue
+                safe.pre.append('assert_eq!({}, {}, "length mismatch of {} and {}");'.format(
+                    first, length_of(array, factor), identifier(arrays[0][0].name), identifier(array.name)
+                ))
+            safe.pre.append('let {} = ({}) as {};'.format(name, first, to_rust_type(type_)))
+            safe.args.append(name)
+        elif param.name in enumerate_counts:
+            if type_.is_pointer != 1 or type_.is_const:
+                return None
+            safe.pre.append('let mut {}: {} = 0;'.format(name, element))
+            safe.args.append('&mut {}'.format(name))
+        elif any(p is param for p in enumerate_counts.values()):
+            init = 'std::mem::zeroed()' if is_void else initializer(ParsedType(
+                None, type_.type, type_.original_type, is_pointer=type_.is_pointer - 1
+            ))
+            if init is None or type_.is_const:
+                return None
+            element = 'u8' if is_void else element
+            safe.pre.append('let mut {}: Vec<{}> = Vec::new();'.format(name, element))
+            safe.args.append('{}.as_mut_ptr() as *mut _'.format(name))
+            safe.enumerate = (name, identifier(param.len), init)
+            safe.outs.append(name)
+            safe.out_types.append('Vec<{}>'.format(element))
+        elif not type_.is_pointer:
+            if type_.is_array:
+                return None
+            safe.params.append('{}: {}'.format(name, to_rust_type(type_)))
+            safe.args.append(name)
+        elif param.name in string_lengths:
+            safe.args.append('{}_lengths.as_ptr()'.format(identifier(string_lengths[param.name].name)))
+        elif length_out is not None and param is length_out:
+            safe.pre.append('let mut {}: {} = 0;'.format(name, element))
+            safe.args.append('&mut {}'.format(name))
+        elif is_char(param) and type_.is_const and type_.is_pointer == 2 and param.len in derived:
+            lengths = [l for l, s in string_lengths.items() if s is param]
+            # strings with a length do not need to be null-terminated
+            safe.params.append('{}: &[{}]'.format(name, '&str' if lengths else '&CStr'))
+            if lengths:
+                safe.pre.append('let {0}_lengths: Vec<{1}> = {0}.iter().map(|s| s.len() as {1}).collect();'.format(
+                    name, element_type(params[lengths[0]].type)
+                ))
+            safe.pre.append('let {0}: Vec<*const {1}> = {0}.iter().map(|s| s.as_ptr() as *const {1}).collect();'.format(
+                name, to_rust_type(type_.type)
+            ))
+            safe.args.append('{}.as_ptr()'.format(name))
+        elif is_char(param) and type_.is_const and type_.is_pointer == 1:
+            if param.len in derived:
+                safe.params.append('{}: &str'.format(name))
+                safe.args.append('{}.as_ptr() as *const _'.format(name))
+            elif is_optional(param):
+                safe.params.append('{}: Option<&CStr>'.format(name))
+                safe.args.append('{}.map_or(std::ptr::null(), |s| s.as_ptr() as *const _)'.format(name))
+            else:
+                safe.params.append('{}: &CStr'.format(name))
+                safe.args.append('{}.as_ptr() as *const _'.format(name))
+        elif is_out_string(param) and param.len in counted:
+            safe.pre.append('let mut {}: Vec<u8> = vec![0; {} as usize];'.format(name, identifier(param.len)))
+            safe.args.append('{}.as_mut_ptr() as *mut _'.format(name))
+            if length_out is not None:
+                safe.post.append('{}.truncate({} as usize);'.format(name, identifier(length_out.name)))
+            else:
+                safe.post.append('if let Some(end) = {0}.iter().position(|&c| c == 0) {{ {0}.truncate(end); }}'.format(name))
+            safe.outs.append('String::from_utf8_lossy(&{}).into_owned()'.format(name))
+            safe.out_types.append('String')
+        elif type_.is_pointer > 1 and type_.is_const and param.len:
+            # arrays of pointers
+            safe.params.append('{}: &[{}]'.format(name, element))
+            safe.args.append('{}.as_ptr()'.format(name))
+        elif param.len and param.len != '1':
+            mutable = '' if type_.is_const else 'mut '
+            if is_void:
+                generic = 'T{}'.format(len(safe.generics))
+                safe.generics.append(generic)
+                element = generic
+            cast = ' as *{} _'.format('const' if type_.is_const else 'mut') if is_void else ''
+            if param.len.isdigit():
+                safe.params.append('{}: &{}[{}; {}]'.format(name, mutable, element, param.len))
+            else:
+                safe.params.append('{}: &{}[{}]'.format(name, mutable, element))
+            safe.args.append('{}.{}(){}'.format(name, 'as_ptr' if type_.is_const else 'as_mut_ptr', cast))
+        elif is_void and type_.is_const:
+            return None
+        elif type_.is_const:
+            typ = resolve(type_.type)
+            if param.len == '1' or (typ is not None and typ.category in ('struct', 'union')):
+                if is_optional(param):
+                    safe.params.append('{}: Option<&{}>'.format(name, element))
+                    safe.args.append('{}.map_or(std::ptr::null(), |r| r)'.format(name))
+                else:
+                    safe.params.append('{}: &{}'.format(name, element))
+                    safe.args.append(name)
+            else:
+                safe.params.append('{}: &[{}]'.format(name, element))
+                safe.args.append('{}.as_ptr()'.format(name))
+        else:
+            if is_void or is_char(param) or type_.is_struct or type_.type in _OPAQUE_TYPES:
+                return None
+            init = initializer(ParsedType(None, type_.type, type_.original_type, is_pointer=type_.is_pointer - 1))
+            if init is None:
+                return None
+            safe.pre.append('let mut {}: {} = {};'.format(name, element, init))
+            safe.args.append('&mut {}'.format(name))
+            safe.outs.append(name)
+            safe.out_types.append(element)
+
+    if safe.enumerate is not None:
+        array, count, init = safe.enumerate
+        safe.count_args = [
+            'std::ptr::null_mut()' if arg.startswith(array + '.') else arg for arg in safe.args
+        ]
+
+    return safe
+
+
+def collect_safe_commands(commands, types_by_name, is_zeroable, groups=None):
+    """
+    Creates the safe wrappers of all commands which can be mapped.
+
+    :param commands: commands of the feature set
+    :param types_by_name: dictionary of the types of the feature set by name
+    :param is_zeroable: function checking if a type is valid when zero initialized, see `zeroable_checker`
+    :param groups: dictionary of generated enum groups by name
+    :return: list of SafeCommand
+    """
+    safe_commands = (to_safe_command(c, types_by_name, is_zeroable, groups) for c in commands)
+    return [safe for safe in safe_commands if safe is not None]
+
+
+_FUNCPOINTER_RE = re.compile(r'\(\s*(APIENTRY\s*)?\*\s*\w+\s*\)\s*\(')
+
+
+def is_funcpointer(typ):
+    # only Vulkan categorizes function pointer types
+    return typ.category == 'funcpointer' or bool(_FUNCPOINTER_RE.search(getattr(typ, '_raw', None) or ''))
+
+
+class TracedParam(object):
+    """
+    Description of how a parameter is written into a trace:
+
+    - `value`: the bytes of the value
+    - `string`: the null-terminated string the pointer points to
+    - `input`: `count` elements the pointer points to
+    - `output`: the size of the buffer the pointer points to, `count` elements or unknown (None)
+    - `strings`: `count` strings, null-terminated or with their lengths in the `lengths` parameter
+    - `address`: the address of the pointer
+
+    Values, inputs and outputs naming objects have the kind of the `object`,
+    arrays of objects also the `element` type. Values and addresses of `native`
+    handles are only valid in the recording process.
+    """
+    def __init__(self, name, kind, count=None, lengths=None, object=None, element=None, native=False):
+        self.name = name
+        self.kind = kind
+        self.count = count
+        self.lengths = lengths
+        self.object = object
+        self.element = element
+        self.native = native
+
+
+class TracedCommand(object):
+    def __init__(self, command, params, replayable, ret_object=None):
+        self.command = command
+        self.params = params
+        # callbacks and pointers without a payload, besides buffer offsets, can not be replayed
+        self.replayable = replayable
+        # kind of the object the command returns
+        self.ret_object = ret_object
+
+    @property
+    def created(self):
+        """ output parameters the command writes new objects to """
+        return [param for param in self.params if param.kind == 'output' and param.object]
+
+    @property
+    def creates(self):
+        return self.ret_object is not None or len(self.created) > 0
+
+
+# pointer parameters of GL which are offsets into the bound buffer object, if there is one
+_GL_BUFFER_OFFSETS = ('pointer', 'indices', 'indirect', 'pixels')
+# EGL handles, these are not categorized in the specification
+_EGL_HANDLES = (
+    'EGLConfig', 'EGLContext', 'EGLDeviceEXT', 'EGLDisplay', 'EGLImage', 'EGLImageKHR',
+    'EGLOutputLayerEXT', 'EGLOutputPortEXT', 'EGLStreamKHR', 'EGLSurface', 'EGLSync', 'EGLSyncKHR'
+)
+# types and parameters of the native handles EGL is passed
+_EGL_NATIVES = (
+    'EGLNativeDisplayType', 'EGLNativePixmapType', 'EGLNativeWindowType',
+    'native_display', 'native_pixmap', 'native_window'
+)
+# attribute lists of EGL are terminated by `EGL_NONE`
+_EGL_ATTRIB_LIST = ('attrib_list', '0x3038')
+# arrays of EGL the specification has no `len` for, written to buffers of their size when replaying
+_EGL_LENS = {
+    ('eglChooseConfig', 'configs'): 'config_size',
+    ('eglGetConfigs', 'configs'): 'config_size',
+    ('eglGetOutputLayersEXT', 'layers'): 'max_layers',
+    ('eglGetOutputPortsEXT', 'ports'): 'max_ports',
+    ('eglQueryDevicesEXT', 'devices'): 'max_devices',
+    ('eglQueryDmaBufFormatsEXT', 'formats'): 'max_formats',
+    ('eglQueryDmaBufModifiersEXT', 'modifiers'): 'max_modifiers',
+    ('eglQueryDmaBufModifiersEXT', 'external_only'): 'max_modifiers',
+    ('eglQuerySupportedCompressionRatesEXT', 'rates'): 'rate_size',
+    ('eglGetCompositorTimingANDROID', 'values'): 'numTimestamps',
+    ('eglGetFrameTimestampsANDROID', 'values'): 'numTimestamps',
+    ('eglQueryDeviceBinaryEXT', 'value'): 'max_size',
+    ('eglQueryStreamMetadataNV', 'data'): 'size',
+    ('eglSetDamageRegionKHR', 'rects'): 'n_rects*4',
+    # images have at most four planes
+    ('eglExportDMABUFImageMESA', 'fds'): '4',
+    ('eglExportDMABUFImageMESA', 'strides'): '4',
+    ('eglExportDMABUFImageMESA', 'offsets'): '4',
+    ('eglExportDMABUFImageQueryMESA', 'modifiers'): '4',
+}
+
+
+def to_traced_command(command, types_by_name, buffer_offsets=(), handles=(), natives=(),
+                      attrib_list=None, lens=None):
+    """
+    Uses the `len` information of the specification to decide which
+    pointer payloads of a command can be written into a trace.
+
+    Object names of GL are identified by their class, Vulkan handles by their type
+    category, the handles of other specifications have to be passed in `handles`.
+
+    :param command: the command
+    :param types_by_name: dictionary of the types of the feature set by name
+    :param buffer_offsets: names of the pointer parameters which may be offsets into buffer objects,
+                           their address is recorded and passed as is when replaying
+    :param handles: names of the types which are handles
+    :param natives: names of the types and pointer parameters which are native handles,
+                    calls are only replayed if these are null
+    :param attrib_list: tuple of the name of the attribute list parameters and the key they are terminated with
+    :param lens: lengths of the array parameters missing in the specification, by command and parameter name
+    :return: a TracedCommand
+    """
+    lens = lens or {}
+
+    def resolve(name):
+        typ = types_by_name.get(name)
+        while typ is not None and typ.alias is not None:
+            typ = types_by_name.get(typ.alias)
+        return typ
+
+    def object_of(type_name, object_class):
+        if object_class:
+            return object_class
+        typ = resolve(type_name)
+        if typ is not None and (typ.category == 'handle' or typ.name in handles):
+            return typ.name
+        return None
+
+    params = dict((p.name, p) for p in command.params)
+
+    def count_of(param):
+        length = param.len or lens.get((command.name, param.name))
+        if not length:
+            return None if param.type.is_const else '1'
+        if length.isdigit():
+            return length
+
+        match = _LEN_FACTOR_RE.match(length)
+        count, factor = (match.group(1), int(match.group(2))) if match else (length.split(',')[0], 1)
+        if count not in params or params[count].type.is_array:
+            return None
+
+        name = identifier(count)
+        if params[count].type.is_pointer == 1:
+            count = 'if {0}.is_null() {{ 0 }} else {{ *{0} as i64 }}'.format(name)
+            return count if factor == 1 else '({}) * {}'.format(count, factor)
+        elif params[count].type.is_pointer:
+            return None
+
+        count = '{} as i64'.format(name)
+        return count if factor == 1 else '{} * {}'.format(count, factor)
+
+    traced = list()
+    replayable = True
+    for param in command.params:
+        name = identifier(param.name)
+        type_ = param.type
+
+        typ = resolve(type_.type)
+        if typ is not None and is_funcpointer(typ) and not type_.is_pointer:
+            replayable = False
+
+        if (not type_.is_pointer and type_.type in natives) or param.name in natives:
+            traced.append(TracedParam(name, 'address' if type_.is_pointer else 'value', native=True))
+        elif not type_.is_pointer:
+            traced.append(TracedParam(name, 'value', object=object_of(type_.type, param.object_class)))
+        elif type_.is_pointer == 2 and type_.type in _CHAR_TYPES and type_.is_const and count_of(param):
+            lengths = next((p for p in command.params if p.name == 'length' and p.len == param.len
+                            and p.type.is_pointer == 1 and p.type.is_const), None)
+            traced.append(TracedParam(
+                name, 'strings', count_of(param), None if lengths is None else identifier(lengths.name)
+            ))
+        elif type_.is_pointer > 1 or (typ is not None and typ.category in ('struct', 'union') and type_.is_const):
+            # pointers inside of the payload would not be valid when replaying
+            traced.append(TracedParam(name, 'address'))
+            replayable = False
+        elif type_.type in _CHAR_TYPES and type_.is_const and param.len in (None, 'null-terminated'):
+            traced.append(TracedParam(name, 'string'))
+        elif not type_.is_const:
+            count = count_of(param)
+            # the created objects can only be recorded if their number is known
+            obj = object_of(type_.type, param.object_class) if count else None
+            traced.append(TracedParam(name, 'output', count, object=obj, element=obj and type_.type))
+        elif attrib_list is not None and param.name == attrib_list[0] and not param.len:
+            count = 'attrib_list_len({}, {})'.format(name, attrib_list[1])
+            traced.append(TracedParam(name, 'input', count))
+        else:
+            count = count_of(param)
+            obj = object_of(type_.type, param.object_class) if count else None
+            traced.append(TracedParam(
+                name, 'address' if count is None else 'input', count, object=obj, element=obj and type_.type
+            ))
+            if count is None and param.name not in buffer_offsets:
+                replayable = False
+
+    ret = command.proto.ret
+    ret_object = object_of(ret.type, command.proto.object_class) if not ret.is_pointer else None
+
+    return TracedCommand(command, traced, replayable, ret_object)
+
+
+class GLObject(object):
+    """
+    Owned GL object type, created through `gen` (`glGen*` or `glCreate*`)
+    and deleted through `delete`.
+    """
+    def __init__(self, name, gen, delete, create=None, groups=None):
+        self.name = name
+        self.gen = gen
+        self.delete = delete
+        # direct state access variant of a `glGen*` command
+        self.create = create
+        self.groups = groups
+
+    @property
+    def single(self):
+        # `glCreateShader` returns the name, `glGenBuffers` fills an array
+        return self.gen.proto.ret.type == 'GLuint'
+
+    @property
+    def gen_params(self):
+        return self._params(self.gen.params if self.single else self.gen.params[:-2])
+
+    @property
+    def create_params(self):
+        return self._params(self.create.params[:-2] if self.create else [])
+
+    def _params(self, params):
+        """ list of (name, type) tuples of the parameters passed through to the command """
+        return [(identifier(param.name), to_rust_type(to_param_type(param, self.groups))) for param in params]
+
+
+# extension commands have a vendor suffix, e.g. `glGenBuffersARB`
+_VENDOR_SUFFIX_RE = re.compile(r'[A-Z]{2,}$')
+
+
+def _is_array_of_names(params, is_pointer):
+    return len(params) == 2 and params[0].type.type == 'GLsizei' and \
+        params[1].type.type == 'GLuint' and bool(params[1].type.is_pointer) == is_pointer
+
+
+def _singular(name):
+    return name[:-3] + 'y' if name.endswith('ies') else name[:-1]
+
+
+def collect_gl_objects(commands, groups=None):
+    """
+    Pairs the commands creating and deleting GL objects, e.g. `glGenBuffers`/`glDeleteBuffers`
+    or `glCreateShader`/`glDeleteShader`, extension commands are ignored.
+
+    :param commands: commands of the feature set
+    :param groups: dictionary of generated enum groups by name
+    :return: list of GLObject
+    """
+    by_name = dict((command.name, command) for command in commands)
+
+    result = []
+    for command in commands:
+        if _VENDOR_SUFFIX_RE.search(command.name):
+            continue
+
+        if command.name.startswith('glGen'):
+            names = command.name[len('glGen'):]
+            delete = by_name.get('glDelete' + names)
+            if delete is None or not names.endswith('s') or command.proto.ret.type != 'void' or \
+                    not _is_array_of_names(command.params, True) or \
+                    not _is_array_of_names(delete.params, True):
+                continue
+
+            create = by_name.get('glCreate' + names)
+            if create is not None and (create.proto.ret.type != 'void' or
+                                       not _is_array_of_names(create.params[-2:], True)):
+                create = None
+
+            result.append(GLObject(_singular(names), command, delete, create=create, groups=groups))
+        elif command.name.startswith('glCreate'):
+            name = command.name[len('glCreate'):]
+            delete = by_name.get('glDelete' + name)
+            if delete is None or command.proto.ret.type != 'GLuint' or \
+                    len(delete.params) != 1 or delete.params[0].type.type != 'GLuint':
+                continue
+
+            result.append(GLObject(name, command, delete, groups=groups))
+
+    return sorted(result, key=lambda o: o.name)
+
+
+class VkObject(object):
+    """
+    Owned Vulkan handle, destroyed through `destroy` on the dispatch table `table`
+    (`Instance` or `Device`), the handle keeps a reference to its `parent`, either
+    the dispatch table or another VkObject (e.g. the `CommandPool` of a `CommandBuffer`).
+    """
+    def __init__(self, handle, table_handle, destroy):
+        self.handle = handle
+        self.name = handle[2:]
+        self.table_handle = table_handle
+        self.table = _VK_TABLES[table_handle]
+        self.destroy = destroy
+        self.parent = None
+        # arguments of `destroy`
+        self.destroy_args = []
+        # commands creating a single handle, see `VkConstructor`
+        self.constructors = []
+
+    @property
+    def gates(self):
+        """ destroy commands of this handle and its parents, the handle is available if all are """
+        gates = [self.destroy]
+        if isinstance(self.parent, VkObject):
+            gates.extend(gate for gate in self.parent.gates if gate not in gates)
+        return gates
+
+
+class VkConstructor(object):
+    def __init__(self, command, params, args, allocator):
+        self.command = command
+        # (name, type) of the parameters of the constructor, besides parent and allocator
+        self.params = params
+        # arguments of the command
+        self.args = args
+        # whether the command takes allocation callbacks
+        self.allocator = allocator
+
+
+_VK_TABLES = {'VkInstance': 'Instance', 'VkDevice': 'Device'}
+
+
+def _to_vk_destroy_args(obj, params):
+    args = []
+    for param in params:
+        typ = param.type.type
+        if typ in _VK_TABLES and not param.type.is_pointer:
+            args.append('table.handle()')
+        elif typ == obj.handle:
+            args.append('&self.handle' if param.type.is_pointer else 'self.handle')
+        elif isinstance(obj.parent, VkObject) and typ == obj.parent.handle and not param.type.is_pointer:
+            args.append('self.parent.handle()')
+        elif typ == 'VkAllocationCallbacks':
+            args.append('allocator')
+        elif typ == 'uint32_t' and not param.type.is_pointer:
+            # count of the freed handles
+            args.append('1')
+        else:
+            return None
+    return args
+
+
+def _to_vk_constructor(obj, command):
+    params = command.params
+    if command.proto.ret.type != 'VkResult' or len(params) < 2 or \
+            params[0].type.type != obj.table_handle or params[0].type.is_pointer:
+        return None
+
+    output = params[-1]
+    if output.type.type != obj.handle or output.type.is_pointer != 1 or output.type.is_const or output.len:
+        return None
+
+    result = VkConstructor(command, [], ['table.handle()'], False)
+    for param in params[1:-1]:
+        name = identifier(param.name)
+        if param.type.type == 'VkAllocationCallbacks':
+            result.allocator = True
+            result.args.append('allocation_callbacks(allocator)')
+        elif param.len or param.type.type == 'void' or param.type.is_pointer > 1 or param.type.type in _VK_TABLES:
+            return None
+        elif param.type.is_pointer:
+            # create infos are passed by reference
+            result.params.append((name, '&' + to_rust_type(ParsedType.from_string(param.type.type))))
+            result.args.append(name)
+        else:
+            result.params.append((name, to_rust_type(param.type)))
+            result.args.append(name)
+
+    result.args.append('&mut handle')
+    return result
+
+
+def collect_vk_objects(types, commands):
+    """
+    Pairs the handle types with the commands destroying them (`vkDestroy*` or `vkFree*`)
+    and creating them, the handles reference their parent as declared in the specification.
+    `VkInstance` and `VkDevice` are owned through their dispatch tables and not part of the result.
+
+    :param types: types of the feature set
+    :param commands: commands of the feature set
+    :return: list of VkObject, parents before their children
+    """
+    by_name = dict((command.name, command) for command in commands)
+    handles = dict((t.name, t) for t in types if t.category == 'handle' and not t.alias)
+
+    objects = dict()
+    for handle in handles:
+        if handle in _VK_TABLES:
+            continue
+
+        name = handle[2:]
+        if name == 'DeviceMemory':
+            candidates = ['vkFreeMemory']
+        else:
+            candidates = ['vkDestroy' + name, 'vkFree' + name + 's', 'vkRelease' + name]
+
+        destroy = next((by_name[c] for c in candidates if c in by_name), None)
+        if destroy is None or not destroy.params or destroy.params[0].type.type not in _VK_TABLES:
+            continue
+
+        objects[handle] = VkObject(handle, destroy.params[0].type.type, destroy)
+
+    for obj in objects.values():
+        parent = (handles[obj.handle].parent or '').split(',')[0]
+        obj.parent = objects.get(parent, obj.table)
+        if isinstance(obj.parent, VkObject) and obj.parent.table != obj.table:
+            obj.parent = obj.table
+
+    def depth(obj):
+        return depth(obj.parent) + 1 if isinstance(obj.parent, VkObject) else 0
+
+    result = []
+    for obj in sorted(objects.values(), key=lambda o: (depth(o), o.name)):
+        obj.destroy_args = _to_vk_destroy_args(obj, obj.destroy.params)
+        if obj.destroy_args is None or (isinstance(obj.parent, VkObject) and obj.parent not in result):
+            continue
+
+        for command in commands:
+            constructor = _to_vk_constructor(obj, command)
+            if constructor is not None:
+                obj.constructors.append(constructor)
+
+        result.append(obj)
+
+    return result
+
+
+class Layout(object):
+    """
+    Type whose size, alignment and field offsets are compared with the C headers,
+    `fields` are pairs of the C and the Rust name and `cfg` the attribute
+    the Rust type is gated with.
+    """
+    def __init__(self, name, fields=None, c_type=None, cfg=None):
+        self.name = name
+        self.fields = fields or []
+        self.c_type = c_type or name
+        self.cfg = cfg
+
+
+def collect_vk_layouts(spec, feature_set):
+    """
+    Collects the layouts of all Vulkan types which are defined the same in Rust and C.
+
+    Platform types are skipped, they require the platform headers,
+    as well as opaque types which can not be instantiated.
+
+    :param spec: the specification
+    :param feature_set: the feature set
+    :return: list of Layout
+    """
+    layouts = []
+    for typ in feature_set.types:
+        if spec.protections(typ, feature_set=feature_set):
+            continue
+
+        if typ.alias is not None or typ.category in ('handle', 'bitmask', 'enum', 'funcpointer'):
+            layouts.append(Layout(typ.name))
+        elif typ.category == 'basetype' and typ.type is not None:
+            layouts.append(Layout(typ.name))
+        elif typ.category in ('struct', 'union') and typ.members:
+            # offsets of bitfields can not be taken
+            fields = [
+                (member.name, identifier(member.name)) for member in typ.members
+                if member.type.bitfield is None
+            ]
+            layouts.append(Layout(typ.name, fields))
+
+    return layouts
+
+
+_RUST_DEFINITION_RE = re.compile(
+    r'^(?P<attributes>(?:#\[[^\n]*\]\n)*)pub (?:type (?P<alias>\w+) = (?P<type>[^;]+);|struct (?P<struct>\w+) \{(?P<body>.*?)^\})',
+    re.MULTILINE | re.DOTALL
+)
+_RUST_FIELD_RE = re.compile(r'^\s*(?:pub )?(\w+):', re.MULTILINE)
+_RUST_CFG_RE = re.compile(r'^#\[cfg\(.*\)\]$', re.MULTILINE)
+_RUST_KEYWORDS = {'type_': 'type', 'ref_': 'ref', 'box_': 'box', 'in_': 'in'}
+
+
+_KHRONOS_TYPEDEF_RE = re.compile(r'^typedef\s[^;]*\b(khronos_\w+)\s*;', re.MULTILINE)
+
+
+def collect_handwritten_layouts(feature_set, source, khrplatform=None):
+    """
+    Collects the layouts of the hand-written Rust types which are also part of the feature set.
+
+    :param feature_set: the feature set
+    :param source: rendered source of the hand-written types
+    :param khrplatform: source of `khrplatform.h`, the khronos types it defines are collected as well
+    :return: list of Layout
+    """
+    types = dict((t.name, t) for t in feature_set.types)
+    known = set(types)
+    if 'khrplatform' in known and khrplatform is not None:
+        known.update(_KHRONOS_TYPEDEF_RE.findall(khrplatform))
+
+    layouts = []
+    for match in _RUST_DEFINITION_RE.finditer(source):
+        name = match.group('alias') or match.group('struct')
+        if name not in known or any(layout.name == name for layout in layouts):
+            continue
+
+        cfg = _RUST_CFG_RE.search(match.group('attributes'))
+        cfg = cfg.group(0) if cfg else None
+
+        if match.group('alias'):
+            # opaque types can not be instantiated
+            if not match.group('type').strip().endswith('c_void') or '*' in match.group('type'):
+                layouts.append(Layout(name, cfg=cfg))
+        else:
+            fields = _RUST_FIELD_RE.findall(match.group('body'))
+            fields = [(_RUST_KEYWORDS.get(field, field), field) for field in fields]
+
+            # some structs are not typedef'd in C
+            raw = (types[name]._raw or '').strip() if name in types else ''
+            c_type = 'struct ' + name if raw.startswith('struct') else None
+            layouts.append(Layout(name, fields, c_type=c_type, cfg=cfg))
+
+    return layouts
+
+
 def identifier(name):
     if name in ('type', 'ref', 'box', 'in'):
         return name + '_'
@@ -174,6 +1777,104 @@ class RustConfig(Config):
         default=False,
         description='Enables support for multiple GL contexts'
     )
+    MX_GLOBAL = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Mimic global GL functions dispatching through a thread-local current context'
+    )
+    DEBUG = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Enables generation of a debug build'
+    )
+    LOADER = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Include internal loaders for APIs'
+    )
+    ON_DEMAND = ConfigOption(
+        converter=bool,
+        default=False,
+        description='On-demand function pointer loading, initialize on use (experimental)'
+    )
+    ENUM_GROUPS = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Types enum parameters with a newtype per enum group'
+    )
+    NO_STD = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates a no_std crate only depending on core'
+    )
+    SAFE = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates a safe module with wrappers taking slices, strings and references'
+    )
+
+    MOCK = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates a mock module, enabled through the `mock` cargo feature, for testing without a driver'
+    )
+
+    TRACE = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates a trace module recording all calls into a file, which can be replayed, ' +
+                    'together with the loader a replay binary is generated'
+    )
+
+    STATS = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Counts the calls and measures the CPU time spent per command'
+    )
+
+    TRACING = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Emits a span per call through the tracing crate, enabled through the `tracing` cargo feature, ' +
+                    'GL errors are emitted as events with the `tracing-gl-errors` cargo feature'
+    )
+
+    OBJECTS = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates owned GL objects and Vulkan handles, which are deleted when dropped'
+    )
+
+    NEWTYPE_ENUMS = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates Vulkan enums as newtypes with associated constants, ' +
+                    'which can hold values unknown to the specification'
+    )
+
+    LAYOUT_TESTS = ConfigOption(
+        converter=bool,
+        default=False,
+        description='Generates tests comparing the layout of the types with the C headers, ' +
+                    'which requires a C compiler to run'
+    )
+
+    __constraints__ = [
+        RequirementConstraint(['MX_GLOBAL'], 'MX'),
+        UnsupportedConstraint(['MX'], 'DEBUG'),
+        UnsupportedConstraint(['MX'], 'ON_DEMAND'),
+        UnsupportedConstraint(['MX'], 'SAFE'),
+        UnsupportedConstraint(['MX'], 'TRACE'),
+        # these require allocations, synchronization or the standard library loader
+        UnsupportedConstraint(['NO_STD'], 'DEBUG'),
+        UnsupportedConstraint(['NO_STD'], 'LOADER'),
+        UnsupportedConstraint(['NO_STD'], 'MOCK'),
+        UnsupportedConstraint(['NO_STD'], 'TRACE'),
+        UnsupportedConstraint(['NO_STD'], 'STATS'),
+        UnsupportedConstraint(['NO_STD'], 'MX_GLOBAL'),
+        UnsupportedConstraint(['NO_STD'], 'ON_DEMAND'),
+        UnsupportedConstraint(['NO_STD'], 'SAFE')
+    ]
 
 
 class RustGenerator(JinjaGenerator):
@@ -185,13 +1886,20 @@ class RustGenerator(JinjaGenerator):
     def __init__(self, *args, **kwargs):
         JinjaGenerator.__init__(self, *args, **kwargs)
 
+        # template arguments of the current `generate`, shared by all templates
+        self._template_arguments = None
+        self._sink = LoggingSink(__name__)
+
         self.environment.filters.update(
             feature=lambda x: 'feature = "{}"'.format(x),
-            enum_type=jinja2_contextfilter(lambda ctx, enum: enum_type(enum, ctx['feature_set'])),
-            enum_value=jinja2_contextfilter(lambda ctx, enum: enum_value(enum, ctx['feature_set'])),
-            type=to_rust_type,
-            params=to_rust_params,
+            type=jinja2_contextfilter(lambda ctx, type_: to_rust_type(type_, ctx['spec'])),
+            params=jinja2_contextfilter(
+                lambda ctx, command, mode='full', group_path='': to_rust_params(
+                    command, mode, ctx['enum_groups'], group_path
+                )
+            ),
             identifier=identifier,
+            setters=jinja2_contextfilter(lambda ctx, typ: to_builder_setters(typ, ctx['spec'])),
             no_prefix=jinja2_contextfilter(lambda ctx, value: strip_specification_prefix(value, ctx['spec']))
         )
 
@@ -208,23 +1916,134 @@ class RustGenerator(JinjaGenerator):
 
         return JinjaGenerator.select(self, spec, api, version, profile, extensions, config, sink=sink)
 
+    def generate(self, spec, feature_set, config, sink=LoggingSink(__name__)):
+        self._sink = sink
+        try:
+            JinjaGenerator.generate(self, spec, feature_set, config, sink=sink)
+        finally:
+            self._template_arguments = None
+            self._sink = LoggingSink(__name__)
+
     def get_template_arguments(self, spec, feature_set, config):
+        # collected once per generation, not for every rendered template
+        if self._template_arguments is None:
+            self._template_arguments = self._collect_template_arguments(spec, feature_set, config, self._sink)
+        return self._template_arguments
+
+    def _collect_template_arguments(self, spec, feature_set, config, sink):
         args = JinjaGenerator.get_template_arguments(self, spec, feature_set, config)
 
+        bitmask_bits, bitmasks = collect_bitmask_information(feature_set.types)
+        default_types, extended_by = collect_struct_information(feature_set.types, config['NEWTYPE_ENUMS'])
+        bitfields = collect_bitfields(feature_set.types)
+        constants = collect_constants(feature_set, sink=sink)
+        enum_groups = collect_enum_groups(feature_set, constants) if config['ENUM_GROUPS'] else dict()
+        types_by_name = dict((t.name, t) for t in feature_set.types)
+        is_zeroable = zeroable_checker(feature_set.types, config['NEWTYPE_ENUMS'])
         args.update(
             version=glad.__version__,
-            aliases=collect_alias_information(feature_set.commands)
+            aliases=collect_alias_information(feature_set.commands),
+            # required for vulkan loader:
+            device_commands=list(filter(is_device_command, feature_set.commands)),
+            dispatch_commands=collect_dispatch_information(feature_set.commands),
+            bitmask_bits=bitmask_bits,
+            bitmasks=bitmasks,
+            default_types=default_types,
+            extended_by=extended_by,
+            bitfields=bitfields,
+            constants=constants,
+            enum_groups=enum_groups,
+            feature_gates=collect_feature_gates(spec, feature_set),
+            cargo_features=collect_cargo_features(feature_set),
+            safe_commands=collect_safe_commands(
+                feature_set.commands, types_by_name, is_zeroable, enum_groups
+            ) if config['SAFE'] else [],
+            traced_commands=[
+                to_traced_command(
+                    command, types_by_name,
+                    buffer_offsets=_GL_BUFFER_OFFSETS if spec.name == 'gl' else (),
+                    handles=_EGL_HANDLES if spec.name == 'egl' else (),
+                    natives=_EGL_NATIVES if spec.name == 'egl' else (),
+                    attrib_list=_EGL_ATTRIB_LIST if spec.name == 'egl' else None,
+                    lens=_EGL_LENS if spec.name == 'egl' else None
+                )
+                for command in feature_set.commands
+            ] if config['TRACE'] else [],
+            objects=self._collect_objects(spec, feature_set, enum_groups) if config['OBJECTS'] else []
         )
+        # the hand-written types are rendered with the other arguments
+        args['layouts'] = self._collect_layouts(spec, feature_set, args) if config['LAYOUT_TESTS'] else []
 
         return args
 
+    def _collect_objects(self, spec, feature_set, enum_groups):
+        if spec.name == 'gl':
+            return collect_gl_objects(feature_set.commands, enum_groups)
+        elif spec.name == 'vk':
+            return collect_vk_objects(feature_set.types, feature_set.commands)
+        return []
+
+    def _collect_layouts(self, spec, feature_set, args):
+        if spec.name == 'vk':
+            return collect_vk_layouts(spec, feature_set)
+
+        # the types of all specifications except Vulkan are hand-written,
+        # rendered like `impl.rs` includes them
+        template = self.environment.from_string(
+            "{% import 'template_utils.rs' as template_utils with context %}"
+            "{% include 'types/' + spec.name + '.rs' %}"
+        )
+
+        with closing(glad.files.open_local('khrplatform.h', 'rb')) as f:
+            khrplatform = f.read().decode('utf-8')
+
+        return collect_handwritten_layouts(feature_set, template.render(**args), khrplatform)
+
     def get_templates(self, spec, feature_set, config):
-        return [
+        templates = [
             ('Cargo.toml', 'glad-{}/Cargo.toml'.format(feature_set.name)),
             ('lib.rs', 'glad-{}/src/lib.rs'.format(feature_set.name)),
             ('impl.rs', 'glad-{}/src/{}.rs'.format(feature_set.name, spec.name))
         ]
 
+        if config['TRACE'] and config['LOADER'] and spec.name in _LOADER_SPECS:
+            templates.append(('replay.rs', 'glad-{}/src/bin/replay.rs'.format(feature_set.name)))
+
+        if config['LAYOUT_TESTS']:
+            templates.extend([
+                ('layout.rs', 'glad-{}/tests/layout.rs'.format(feature_set.name)),
+                ('layout.c', 'glad-{}/tests/layout/layout.c'.format(feature_set.name))
+            ])
+
+        return templates
+
+    def post_generate(self, spec, feature_set, config):
+        if config['LAYOUT_TESTS']:
+            self._generate_c_header(spec, feature_set)
+
+    def _generate_c_header(self, spec, feature_set):
+        """
+        Generates the header only C loader, including the bundled headers,
+        the layout tests are compiled against.
+        """
+        path = os.path.join(self.path, 'glad-{}'.format(feature_set.name), 'tests', 'layout')
+        generator = CGenerator(path, opener=self.opener, gen_info_factory=self.gen_info_factory)
+
+        config = generator.Config()
+        config['HEADER_ONLY'] = True
+        generator.generate(spec, feature_set, config)
+
+        # the GLX and WGL headers include the GL header
+        if spec.name in ('glx', 'wgl'):
+            # a local `gl.xml` is preferred, like for the specifications passed on the command line
+            xml_name = GL.NAME + '.xml'
+            if os.path.isfile(xml_name):
+                gl = GL.from_file(xml_name, opener=self.opener)
+            else:
+                gl = GL.from_remote(opener=self.opener)
+            gl_feature_set = generator.select(gl, 'gl', None, 'compatibility', [], config)
+            generator.generate(gl, gl_feature_set, config)
+
     def modify_feature_set(self, spec, feature_set, config):
         self._remove_empty_enums(feature_set)
 
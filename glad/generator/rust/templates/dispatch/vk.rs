@@ -0,0 +1,139 @@
+{% import 'template_utils.rs' as template_utils with context %}
+pub use self::dispatch::{Entry, Instance, Device};
+
+mod dispatch {
+    #![allow(non_snake_case, unused_variables, dead_code, unused_imports)]
+
+    use core::mem::transmute;
+    use core::ffi::*;
+    {% if options.debug %}
+    use core::fmt::Debug;
+    {% endif %}
+    use super::*;
+    use super::types::*;
+
+    type GetInstanceProcAddr = extern "system" fn(VkInstance, *const c_char) -> *const c_void;
+    type GetDeviceProcAddr = extern "system" fn(VkDevice, *const c_char) -> *const c_void;
+
+    macro_rules! func {
+        ($fun:ident, $cmd:expr, $ret:ty, $($name:ident: $typ:ty),*) => {
+            #[inline] pub unsafe fn $fun(&self, $($name: $typ),*) -> $ret {
+                {% if options.trace %}
+                let created = if trace::is_active() { trace::$fun($($name),*) } else { None };
+                {% endif %}
+                {% if options.debug or options.stats or options.tracing or options.mock or options.trace %}
+                {% if options.tracing %}
+                #[cfg(feature = "tracing")]
+                let span = instrument::$fun($($name),*);
+                #[cfg(feature = "tracing")]
+                let _entered = span.enter();
+                {% endif %}
+                {% if options.debug %}
+                pre_call($cmd, &[$(&$name as &dyn Debug),*]);
+                {% endif %}
+                {% if options.stats %}
+                let start = stats::thread_time();
+                {% endif %}
+                let ret = transmute::<_, extern "system" fn($($typ),*) -> $ret>(self.$fun.ptr)($($name),*);
+                {% if options.mock %}
+                // a handler of the mock panicked
+                #[cfg(feature = "mock")]
+                super::mock::resume_unwind();
+                {% endif %}
+                {% if options.trace %}
+                if let Some(created) = created { created.finish(&ret); }
+                {% endif %}
+                {% if options.stats %}
+                stats::COUNTERS.$fun.record(stats::thread_time().saturating_sub(start));
+                {% endif %}
+                {% if options.tracing %}
+                #[cfg(feature = "tracing")]
+                instrument::finish(&span, $cmd, &ret);
+                {% endif %}
+                {% if options.debug %}
+                post_call(&ret, $cmd, &[$(&$name as &dyn Debug),*]);
+                {% endif %}
+                ret
+                {% else %}
+                transmute::<_, extern "system" fn($($typ),*) -> $ret>(self.$fun.ptr)($($name),*)
+                {% endif %}
+            }
+        }
+    }
+
+{% macro table(name, handle, commands, get_proc_addr) %}
+{% set names = commands|map(attribute='name')|list %}
+    pub struct {{ name }} {
+        {% if handle %}
+        handle: {{ handle }},
+        {% endif %}
+        {% for command in commands %}
+        {{ template_utils.protect(command) }} {{ command.name|no_prefix }}: FnPtr,
+        {% endfor %}
+    }
+
+    impl {{ name }} {
+        unsafe fn new(handle: {{ handle or 'VkInstance' }}, get_proc_addr: {{ get_proc_addr }}) -> {{ name }} {
+            #[allow(unused_mut)]
+            let mut table = {{ name }} {
+                {% if handle %}
+                handle,
+                {% endif %}
+                {% for command in commands %}
+                {{ template_utils.protect(command) }} {{ command.name|no_prefix }}: FnPtr::new(get_proc_addr(handle, "{{ command.name }}\0".as_ptr() as *const c_char)),
+                {% endfor %}
+            };
+
+            {% for command in commands %}
+            {% for alias in aliases.get(command.name, [])|reject('equalto', command.name) if alias in names %}
+            {{ template_utils.protect(command) }}{{ template_utils.protect(alias) }} table.{{ command.name|no_prefix }}.aliased(&table.{{ alias|no_prefix }});
+            {% endfor %}
+            {% endfor %}
+
+            table
+        }
+        {% if handle %}
+
+        pub fn handle(&self) -> {{ handle }} {
+            self.handle
+        }
+        {% endif %}
+
+        {% for command in commands %}
+        {{ template_utils.protect(command) }} func!({{ command.name|no_prefix }}, "{{ command.name }}", {{ command.proto.ret|type }}, {{ command|params }});
+        {% endfor %}
+{{ caller() }}
+    }
+{% endmacro %}
+    /// Global commands, which do not require an instance.
+{% call table('Entry', None, dispatch_commands.entry, 'GetInstanceProcAddr') %}
+
+        /// Creates the entry from a pointer to `vkGetInstanceProcAddr`.
+        pub unsafe fn from_get_instance_proc_addr(get_instance_proc_addr: *const c_void) -> Entry {
+            let mut entry = Entry::new(core::mem::zeroed(), transmute::<_, GetInstanceProcAddr>(get_instance_proc_addr));
+            {{ template_utils.gate('vkGetInstanceProcAddr') }} { entry.GetInstanceProcAddr = FnPtr::new(get_instance_proc_addr); }
+            entry
+        }
+
+        /// Loads the instance level commands of `instance` through `vkGetInstanceProcAddr`.
+        {{ template_utils.gate('vkGetInstanceProcAddr') }}
+        pub unsafe fn load_instance(&self, instance: VkInstance) -> Instance {
+            Instance::new(instance, transmute::<_, GetInstanceProcAddr>(self.GetInstanceProcAddr.ptr))
+        }
+{% endcall %}
+
+    /// Instance level commands of a single `VkInstance`.
+{% call table('Instance', 'VkInstance', dispatch_commands.instance, 'GetInstanceProcAddr') %}
+
+        /// Loads the device level commands of `device` through `vkGetDeviceProcAddr`,
+        /// device commands loaded this way skip the dispatch of the Vulkan loader.
+        {{ template_utils.gate('vkGetDeviceProcAddr') }}
+        pub unsafe fn load_device(&self, device: VkDevice) -> Device {
+            Device::new(device, transmute::<_, GetDeviceProcAddr>(self.GetDeviceProcAddr.ptr))
+        }
+{% endcall %}
+
+    /// Device level commands of a single `VkDevice`.
+{% call table('Device', 'VkDevice', dispatch_commands.device, 'GetDeviceProcAddr') %}
+{% endcall %}
+}
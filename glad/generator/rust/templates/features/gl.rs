@@ -0,0 +1,269 @@
+/// Versions of the feature set supported by the context which was current while loading.
+#[allow(non_snake_case)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Features {
+    {% for feature in feature_set.features %}
+    pub {{ feature.name|no_prefix }}: bool,
+    {% endfor %}
+}
+
+/// Extensions of the feature set supported by the context which was current while loading.
+#[allow(non_snake_case)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Extensions {
+    {% for extension in feature_set.extensions %}
+    pub {{ extension.name|no_prefix }}: bool,
+    {% endfor %}
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Extension {
+    {% for extension in feature_set.extensions %}
+    {{ extension.name|no_prefix }},
+    {% endfor %}
+}
+
+impl Extension {
+    /// Name of the extension as reported by the driver.
+    pub fn name(self) -> &'static str {
+        match self {
+            {% for extension in feature_set.extensions %}
+            Extension::{{ extension.name|no_prefix }} => "{{ extension.name }}",
+            {% endfor %}
+        }
+    }
+
+    fn from_name(name: &[u8]) -> Option<Extension> {
+        match name {
+            {% for extension in feature_set.extensions %}
+            b"{{ extension.name }}" => Some(Extension::{{ extension.name|no_prefix }}),
+            {% endfor %}
+            _ => None,
+        }
+    }
+}
+
+impl Features {
+    fn for_version(api: &str, major: u32, minor: u32) -> Features {
+        Features {
+            {% for feature in feature_set.features %}
+            {{ feature.name|no_prefix }}: ({% for api in feature.api|sort %}api == "{{ api }}"{{ ' || ' if not loop.last }}{% endfor %}) && (major, minor) >= ({{ feature.version.major }}, {{ feature.version.minor }}),
+            {% endfor %}
+        }
+    }
+}
+
+impl Extensions {
+    pub fn has(&self, extension: Extension) -> bool {
+        match extension {
+            {% for extension in feature_set.extensions %}
+            Extension::{{ extension.name|no_prefix }} => self.{{ extension.name|no_prefix }},
+            {% endfor %}
+        }
+    }
+
+    {% if options.mx %}
+    fn set(&mut self, extension: Extension) {
+        match extension {
+            {% for extension in feature_set.extensions %}
+            Extension::{{ extension.name|no_prefix }} => self.{{ extension.name|no_prefix }} = true,
+            {% endfor %}
+        }
+    }
+    {% endif %}
+}
+
+/// Queries the version of the current context and calls `found` for every supported extension.
+///
+/// Returns the API and version of the context, `("gl", 0, 0)` if there is no current context.
+unsafe fn find_features<F>(get_string: FnPtr, get_stringi: FnPtr, get_integerv: FnPtr, mut found: F)
+        -> (&'static str, u32, u32) where F: FnMut(Extension) {
+    const GL_VERSION: types::GLenum = 0x1F02;
+    const GL_EXTENSIONS: types::GLenum = 0x1F03;
+    const GL_NUM_EXTENSIONS: types::GLenum = 0x821D;
+    const PREFIXES: [(&[u8], &str); 4] = [
+        (b"OpenGL ES-CM ", "gles1"),
+        (b"OpenGL ES-CL ", "gles1"),
+        (b"OpenGL ES ", "gles2"),
+        (b"OpenGL SC ", "glsc2"),
+    ];
+
+    if !get_string.is_loaded {
+        return ("gl", 0, 0);
+    }
+    let get_string = core::mem::transmute::<_, extern "system" fn(types::GLenum) -> *const types::GLubyte>(get_string.ptr);
+
+    let version = get_string(GL_VERSION);
+    if version.is_null() {
+        return ("gl", 0, 0);
+    }
+    let version = core::ffi::CStr::from_ptr(version as *const core::ffi::c_char).to_bytes();
+
+    let (api, version) = PREFIXES.iter()
+        .find(|&&(prefix, _)| version.starts_with(prefix))
+        .map(|&(prefix, api)| (api, &version[prefix.len()..]))
+        .unwrap_or(("gl", version));
+
+    let mut numbers = version.split(|&c| c == b'.' || c == b' ')
+        .map(|number| number.iter()
+            .take_while(|c| c.is_ascii_digit())
+            .fold(0u32, |acc, &c| acc * 10 + (c - b'0') as u32));
+    let major = numbers.next().unwrap_or(0);
+    let minor = numbers.next().unwrap_or(0);
+
+    if major >= 3 && get_stringi.is_loaded && get_integerv.is_loaded {
+        let get_stringi = core::mem::transmute::<_, extern "system" fn(types::GLenum, types::GLuint) -> *const types::GLubyte>(get_stringi.ptr);
+        let get_integerv = core::mem::transmute::<_, extern "system" fn(types::GLenum, *mut types::GLint)>(get_integerv.ptr);
+
+        let mut num_extensions = 0;
+        get_integerv(GL_NUM_EXTENSIONS, &mut num_extensions);
+        for index in 0..num_extensions.max(0) as types::GLuint {
+            let name = get_stringi(GL_EXTENSIONS, index);
+            if !name.is_null() {
+                let name = core::ffi::CStr::from_ptr(name as *const core::ffi::c_char).to_bytes();
+                if let Some(extension) = Extension::from_name(name) {
+                    found(extension);
+                }
+            }
+        }
+    } else {
+        let extensions = get_string(GL_EXTENSIONS);
+        if !extensions.is_null() {
+            let extensions = core::ffi::CStr::from_ptr(extensions as *const core::ffi::c_char).to_bytes();
+            extensions.split(|&c| c == b' ').filter_map(Extension::from_name).for_each(found);
+        }
+    }
+
+    (api, major, minor)
+}
+
+{% import 'template_utils.rs' as template_utils with context %}
+{% macro command_ptr(name) -%}
+{% if name not in feature_set.commands -%}
+FnPtr::new(core::ptr::null())
+{%- elif options.mx -%}
+{{ template_utils.gated_fn_ptr(name, 'ctx.' + name|no_prefix) }}
+{%- else -%}
+{{ template_utils.gated_fn_ptr(name, template_utils.fn_ptr(name)) }}
+{%- endif %}
+{%- endmacro %}
+{% if options.mx %}
+unsafe fn load_features(ctx: &mut functions::{{ ctx_name }}) {
+    let mut extensions = Extensions::default();
+    let (api, major, minor) = find_features(
+        {{ command_ptr('glGetString') }}, {{ command_ptr('glGetStringi') }}, {{ command_ptr('glGetIntegerv') }},
+        |extension| extensions.set(extension)
+    );
+
+    ctx.version = (major, minor);
+    ctx.features = Features::for_version(api, major, minor);
+    ctx.extensions = extensions;
+}
+
+impl functions::{{ ctx_name }} {
+    /// Version of the context which was current while loading, `(0, 0)` if there was none.
+    pub fn version(&self) -> (u32, u32) {
+        self.version
+    }
+
+    pub fn features(&self) -> Features {
+        self.features
+    }
+
+    pub fn extensions(&self) -> Extensions {
+        self.extensions
+    }
+
+    pub fn has_extension(&self, extension: Extension) -> bool {
+        self.extensions.has(extension)
+    }
+}
+{% if options.mx_global %}
+
+/// Version of the current context, `(0, 0)` if there is none.
+pub fn version() -> (u32, u32) {
+    current::with(|ctx| ctx.map_or((0, 0), |ctx| ctx.version()))
+}
+
+pub fn features() -> Features {
+    current::with(|ctx| ctx.map(|ctx| ctx.features()).unwrap_or_default())
+}
+
+pub fn extensions() -> Extensions {
+    current::with(|ctx| ctx.map(|ctx| ctx.extensions()).unwrap_or_default())
+}
+
+pub fn has_extension(extension: Extension) -> bool {
+    current::with(|ctx| ctx.map_or(false, |ctx| ctx.has_extension(extension)))
+}
+{% endif %}
+{% else %}
+mod supported {
+    use core::sync::atomic::{AtomicBool, AtomicU32};
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const NOT_SUPPORTED: AtomicBool = AtomicBool::new(false);
+
+    /// `(major << 16) | minor`
+    pub static VERSION: AtomicU32 = AtomicU32::new(0);
+    pub static FEATURES: [AtomicBool; {{ feature_set.features|length }}] = [NOT_SUPPORTED; {{ feature_set.features|length }}];
+    pub static EXTENSIONS: [AtomicBool; {{ feature_set.extensions|length }}] = [NOT_SUPPORTED; {{ feature_set.extensions|length }}];
+}
+
+fn reset_features() {
+    use core::sync::atomic::Ordering;
+
+    supported::VERSION.store(0, Ordering::Relaxed);
+    for supported in supported::FEATURES.iter().chain(supported::EXTENSIONS.iter()) {
+        supported.store(false, Ordering::Relaxed);
+    }
+}
+
+unsafe fn load_features() {
+    use core::sync::atomic::Ordering;
+
+    reset_features();
+
+    let (api, major, minor) = find_features(
+        {{ command_ptr('glGetString') }}, {{ command_ptr('glGetStringi') }}, {{ command_ptr('glGetIntegerv') }},
+        |extension| supported::EXTENSIONS[extension as usize].store(true, Ordering::Relaxed)
+    );
+
+    let features = Features::for_version(api, major, minor);
+    supported::VERSION.store((major << 16) | minor, Ordering::Relaxed);
+    {% for feature in feature_set.features %}
+    supported::FEATURES[{{ loop.index0 }}].store(features.{{ feature.name|no_prefix }}, Ordering::Relaxed);
+    {% endfor %}
+}
+
+/// Version of the context which was current while loading, `(0, 0)` if there was none.
+pub fn version() -> (u32, u32) {
+    let version = supported::VERSION.load(core::sync::atomic::Ordering::Relaxed);
+    (version >> 16, version & 0xffff)
+}
+
+pub fn features() -> Features {
+    use core::sync::atomic::Ordering;
+
+    Features {
+        {% for feature in feature_set.features %}
+        {{ feature.name|no_prefix }}: supported::FEATURES[{{ loop.index0 }}].load(Ordering::Relaxed),
+        {% endfor %}
+    }
+}
+
+pub fn extensions() -> Extensions {
+    use core::sync::atomic::Ordering;
+
+    Extensions {
+        {% for extension in feature_set.extensions %}
+        {{ extension.name|no_prefix }}: supported::EXTENSIONS[Extension::{{ extension.name|no_prefix }} as usize].load(Ordering::Relaxed),
+        {% endfor %}
+    }
+}
+
+pub fn has_extension(extension: Extension) -> bool {
+    supported::EXTENSIONS[extension as usize].load(core::sync::atomic::Ordering::Relaxed)
+}
+{% endif %}
@@ -3,9 +3,17 @@ pub use self::types::*;
 pub use self::enumerations::*;
 pub use self::functions::*;
 
-use std::os::raw::c_void;
+use core::ffi::c_void;
+{% if options.debug %}
+use core::sync::atomic::{AtomicPtr, Ordering};
+{% endif %}
 
 {% set ctx_name = feature_set.name | capitalize %}
+{% if options.on_demand %}
+{% set command_ptr = 'on_demand::ptr($cmd, &storage::$fun)' %}
+{% else %}
+{% set command_ptr = 'self.$fun.ptr' if options.mx else 'storage::get(&storage::$fun).ptr' %}
+{% endif %}
 
 #[derive(Copy, Clone)]
 struct FnPtr {
@@ -23,10 +31,6 @@ impl FnPtr {
         }
     }
 
-    fn set_ptr(&mut self, ptr: *const c_void) {
-        *self = Self::new(ptr);
-    }
-    
     fn aliased(&mut self, other: &FnPtr) {
         if !self.is_loaded && other.is_loaded {
             *self = *other;
@@ -40,6 +44,82 @@ impl FnPtr {
 unsafe impl Sync for FnPtr {}
 unsafe impl Send for FnPtr {}
 
+/// Error returned by the checked `try_` variants if the called command is not loaded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NotLoaded {
+    pub name: &'static str
+}
+
+impl core::fmt::Display for NotLoaded {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{{ feature_set.name }}: function {} not loaded", self.name)
+    }
+}
+
+{% if not options.no_std %}
+impl std::error::Error for NotLoaded {}
+{% endif %}
+
+{% if options.debug %}
+pub type PreCallback = fn(name: &'static str, args: &[&dyn core::fmt::Debug]);
+pub type PostCallback = fn(ret: &dyn core::fmt::Debug, name: &'static str, args: &[&dyn core::fmt::Debug]);
+
+static PRE_CALL_CALLBACK: AtomicPtr<()> = AtomicPtr::new(pre_call_callback_default as *mut ());
+static POST_CALL_CALLBACK: AtomicPtr<()> = AtomicPtr::new(post_call_callback_default as *mut ());
+
+#[allow(unused_variables)]
+fn pre_call_callback_default(name: &'static str, args: &[&dyn core::fmt::Debug]) {
+    {% if spec.name == 'gl' and 'glGetError' in feature_set.commands %}
+    let get_error = {{ template_utils.gated_fn_ptr('glGetError', template_utils.fn_ptr('glGetError')) }};
+    if !get_error.is_loaded {
+        eprintln!("GLAD: ERROR glGetError is NULL!");
+        return;
+    }
+
+    unsafe { core::mem::transmute::<_, extern "system" fn() -> types::GLenum>(get_error.ptr)() };
+    {% endif %}
+}
+
+#[allow(unused_variables)]
+fn post_call_callback_default(ret: &dyn core::fmt::Debug, name: &'static str, args: &[&dyn core::fmt::Debug]) {
+    {% if spec.name == 'gl' and 'glGetError' in feature_set.commands %}
+    let get_error = {{ template_utils.gated_fn_ptr('glGetError', template_utils.fn_ptr('glGetError')) }};
+    if !get_error.is_loaded {
+        return;
+    }
+
+    const GL_NO_ERROR: types::GLenum = 0;
+    let error_code = unsafe { core::mem::transmute::<_, extern "system" fn() -> types::GLenum>(get_error.ptr)() };
+
+    if error_code != GL_NO_ERROR {
+        eprintln!("GLAD: ERROR {} in {} with arguments {:?}!", error_code, name, args);
+    }
+    {% endif %}
+}
+
+/// Sets the callback which is invoked before every {{ feature_set.name }} command.
+pub fn set_pre_callback(cb: PreCallback) {
+    PRE_CALL_CALLBACK.store(cb as *mut (), Ordering::Relaxed);
+}
+
+/// Sets the callback which is invoked after every {{ feature_set.name }} command.
+pub fn set_post_callback(cb: PostCallback) {
+    POST_CALL_CALLBACK.store(cb as *mut (), Ordering::Relaxed);
+}
+
+#[inline]
+fn pre_call(name: &'static str, args: &[&dyn core::fmt::Debug]) {
+    let cb = unsafe { core::mem::transmute::<*mut (), PreCallback>(PRE_CALL_CALLBACK.load(Ordering::Relaxed)) };
+    cb(name, args)
+}
+
+#[inline]
+fn post_call(ret: &dyn core::fmt::Debug, name: &'static str, args: &[&dyn core::fmt::Debug]) {
+    let cb = unsafe { core::mem::transmute::<*mut (), PostCallback>(POST_CALL_CALLBACK.load(Ordering::Relaxed)) };
+    cb(ret, name, args)
+}
+{% endif %}
+
 pub mod types {
     {% include 'types/' + spec.name + '.rs' ignore missing with context %}
 }
@@ -47,35 +127,98 @@ pub mod types {
 pub mod enumerations {
     #![allow(dead_code, non_upper_case_globals, unused_imports)]
 
-    use std::os::raw::*;
+    use core::ffi::*;
     use super::types::*;
 
-    {% for enum in feature_set.enums %}
-    pub const {{ enum.name|no_prefix }}: {{ enum|enum_type }} = {{ enum|enum_value }};
+    {% for enum in feature_set.enums if enum.name in constants %}
+    {% set constant = constants[enum.name] %}
+    {{ template_utils.gate(enum) }} pub const {{ enum.name|no_prefix }}: {{ constant.type }} = {{ constant.value }};
     {% endfor %}
 }
 
 pub mod functions {
     #![allow(non_snake_case, unused_variables, dead_code, unused_imports)]
 
-    use std::mem::transmute;
-    use std::os::raw::*;
+    use core::mem::transmute;
+    use core::ffi::*;
+    {% if options.debug %}
+    use core::fmt::Debug;
+    {% endif %}
     use super::*;
     use super::types::*;
 
     macro_rules! func {
-        ($fun:ident, $ret:ty, $($name:ident: $typ:ty),*) => {
+        ($fun:ident, $cmd:expr, $ret:ty, $($name:ident: $typ:ty),*) => {
             #[inline] pub unsafe fn $fun({{ '&self, ' if options.mx }}$($name: $typ),*) -> $ret {
-                transmute::<_, extern "system" fn($($typ),*) -> $ret>({{ 'self.' if options.mx else 'storage::' }}$fun.ptr)($($name),*)
+                {% if options.trace %}
+                let created = if trace::is_active() { trace::$fun($($name),*) } else { None };
+                {% endif %}
+                {% if options.debug or options.stats or options.tracing or options.mock or options.trace %}
+                {% if options.tracing %}
+                #[cfg(feature = "tracing")]
+                let span = instrument::$fun($($name),*);
+                #[cfg(feature = "tracing")]
+                let _entered = span.enter();
+                {% endif %}
+                {% if options.debug %}
+                pre_call($cmd, &[$(&$name as &dyn Debug),*]);
+                {% endif %}
+                {% if options.stats %}
+                let start = stats::thread_time();
+                {% endif %}
+                let ret = transmute::<_, extern "system" fn($($typ),*) -> $ret>({{ command_ptr }})($($name),*);
+                {% if options.mock %}
+                // a handler of the mock panicked
+                #[cfg(feature = "mock")]
+                super::mock::resume_unwind();
+                {% endif %}
+                {% if options.trace %}
+                if let Some(created) = created { created.finish(&ret); }
+                {% endif %}
+                {% if options.stats %}
+                {{ 'self.stats' if options.mx else 'stats::COUNTERS' }}.$fun.record(stats::thread_time().saturating_sub(start));
+                {% endif %}
+                {% if options.tracing %}
+                #[cfg(feature = "tracing")]
+                instrument::finish(&span, $cmd, &ret{% if spec.name == 'gl' and 'glGetError' in feature_set.commands %}, {{ template_utils.gated_fn_ptr('glGetError', 'self.GetError' if options.mx else template_utils.fn_ptr('glGetError')) }}{% endif %});
+                {% endif %}
+                {% if options.debug %}
+                post_call(&ret, $cmd, &[$(&$name as &dyn Debug),*]);
+                {% endif %}
+                ret
+                {% else %}
+                transmute::<_, extern "system" fn($($typ),*) -> $ret>({{ command_ptr }})($($name),*)
+                {% endif %}
+            }
+        }
+    }
+
+    macro_rules! try_func {
+        ($try_fun:ident, $fun:ident, $cmd:expr, $ret:ty, $($name:ident: $typ:ty),*) => {
+            #[inline] pub unsafe fn $try_fun({{ '&self, ' if options.mx }}$($name: $typ),*) -> Result<$ret, NotLoaded> {
+                if !{{ 'self.$fun.is_loaded' if options.mx else 'super::is_loaded::$fun()' }} {
+                    return Err(NotLoaded { name: $cmd });
+                }
+
+                Ok({{ 'self.' if options.mx else 'super::' }}$fun($($name),*))
             }
         }
     }
 
     {% if options.mx %}
+    #[derive(Clone)]
     pub struct {{ ctx_name }} {
         {% for command in feature_set.commands %}
         {{ template_utils.protect(command) }} pub(super) {{ command.name|no_prefix }}: FnPtr,
         {% endfor %}
+        {% if options.stats %}
+        pub(super) stats: std::sync::Arc<stats::Counters>,
+        {% endif %}
+        {% if spec.name == 'gl' %}
+        pub(super) version: (u32, u32),
+        pub(super) features: Features,
+        pub(super) extensions: Extensions,
+        {% endif %}
     }
 
     {% if not spec.name | capitalize == ctx_name %}
@@ -86,22 +229,172 @@ pub mod functions {
     {% endif %}
 
     {% for command in feature_set.commands %}
-    {{ template_utils.protect(command) }} func!({{ command.name|no_prefix }}, {{ command.proto.ret|type }}, {{ command|params }});
+    {{ template_utils.protect(command) }} func!({{ command.name|no_prefix }}, "{{ command.name }}", {{ command.proto.ret|type }}, {{ command|params }});
+    {% endfor %}
+
+    {% if options.mx %}
+    {% for command in feature_set.commands %}
+    {{ template_utils.protect(command) }} try_func!(try_{{ command.name|no_prefix }}, {{ command.name|no_prefix }}, "{{ command.name }}", {{ command.proto.ret|type }}, {{ command|params }});
+    {% endfor %}
+
+    {% for command in feature_set.commands %}
+    {{ template_utils.protect(command) }} #[inline] pub fn is_loaded_{{ command.name|no_prefix }}(&self) -> bool { self.{{ command.name|no_prefix }}.is_loaded }
+    {% endfor %}
+    }
+    {% endif %}
+
+    {% if options.mx_global %}
+    macro_rules! global_func {
+        ($fun:ident, $ret:ty, $($name:ident: $typ:ty),*) => {
+            #[inline] pub unsafe fn $fun($($name: $typ),*) -> $ret {
+                current::with(|ctx| match ctx {
+                    Some(ctx) => ctx.$fun($($name),*),
+                    None => current::none()
+                })
+            }
+        }
+    }
+
+    {% for command in feature_set.commands %}
+    {{ template_utils.protect(command) }} global_func!({{ command.name|no_prefix }}, {{ command.proto.ret|type }}, {{ command|params }});
     {% endfor %}
+    {% endif %}
+
+    {% if not options.mx or options.mx_global %}
+    /// Checked variants of the commands, returning `NotLoaded` instead of panicking.
+    pub mod try_ {
+        use super::*;
+
+        {% if options.mx_global %}
+        macro_rules! try_func {
+            ($try_fun:ident, $fun:ident, $cmd:expr, $ret:ty, $($name:ident: $typ:ty),*) => {
+                #[inline] pub unsafe fn $try_fun($($name: $typ),*) -> Result<$ret, NotLoaded> {
+                    if !super::is_loaded::$fun() {
+                        return Err(NotLoaded { name: $cmd });
+                    }
+
+                    Ok(super::$fun($($name),*))
+                }
+            }
+        }
+        {% endif %}
+
+        {% for command in feature_set.commands %}
+        {{ template_utils.protect(command) }} try_func!({{ command.name|no_prefix }}, {{ command.name|no_prefix }}, "{{ command.name }}", {{ command.proto.ret|type }}, {{ command|params }});
+        {% endfor %}
+    }
+
+    /// Whether a command was loaded and can be called.
+    pub mod is_loaded {
+        use super::*;
+
+        {% for command in feature_set.commands %}
+        {% if options.mx_global %}
+        {{ template_utils.protect(command) }} #[inline] pub fn {{ command.name|no_prefix }}() -> bool { current::with(|ctx| ctx.map_or(false, |ctx| ctx.{{ command.name|no_prefix }}.is_loaded)) }
+        {% else %}
+        {{ template_utils.protect(command) }} #[inline] pub fn {{ command.name|no_prefix }}() -> bool { {{ template_utils.fn_ptr(command.name) }}.is_loaded }
+        {% endif %}
+        {% endfor %}
+    }
+    {% endif %}
+}
+
+{% if options.mx_global %}
+/// Context the free functions of this thread dispatch through.
+mod current {
+    use std::cell::RefCell;
+    use super::functions::{{ ctx_name }};
+
+    std::thread_local! {
+        // contexts made current on this thread, the last one is current,
+        // each is borrowed by the guard returned from `make_current`
+        static CURRENT: RefCell<Vec<*const {{ ctx_name }}>> = const { RefCell::new(Vec::new()) };
+    }
+
+    #[inline]
+    pub(super) fn with<R>(f: impl FnOnce(Option<&{{ ctx_name }}>) -> R) -> R {
+        // the borrow is not held during `f`, which may make another context current
+        let ctx = CURRENT.with(|current| current.borrow().last().copied());
+        f(ctx.map(|ctx| unsafe { &*ctx }))
+    }
+
+    pub(super) fn push(ctx: &{{ ctx_name }}) {
+        CURRENT.with(|current| current.borrow_mut().push(ctx))
+    }
+
+    pub(super) fn remove(ctx: &{{ ctx_name }}) {
+        CURRENT.with(|current| {
+            let mut current = current.borrow_mut();
+            if let Some(index) = current.iter().rposition(|&other| core::ptr::eq(other, ctx)) {
+                current.remove(index);
+            }
+        })
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub(super) fn none() -> ! { panic!("{{ feature_set.name }}: no current context") }
+}
+
+/// Makes `ctx` current on this thread, the free functions dispatch through it
+/// until the returned guard is dropped.
+///
+/// The guard borrows the context, it must not be leaked (e.g. with `mem::forget`)
+/// since the context would stay current after the borrow ended.
+pub fn make_current(ctx: &functions::{{ ctx_name }}) -> Current<'_> {
+    current::push(ctx);
+    Current { ctx, _not_send: std::marker::PhantomData }
+}
 
-    {{ '}' if options.mx }}
+/// Whether a context is current on this thread.
+pub fn has_current() -> bool {
+    current::with(|ctx| ctx.is_some())
 }
 
+/// Guard returned by `make_current`, the context is no longer current on this thread
+/// when dropped and the context made current before it is current again.
+#[must_use]
+pub struct Current<'a> {
+    ctx: &'a functions::{{ ctx_name }},
+    // the context has to be removed from the thread it was made current on
+    _not_send: std::marker::PhantomData<*const ()>
+}
+
+impl Drop for Current<'_> {
+    fn drop(&mut self) {
+        current::remove(self.ctx);
+    }
+}
+
+{% endif %}
 {% if not options.mx %}
+/// Function pointers of the commands, null if a command is not loaded.
 mod storage {
-    #![allow(non_snake_case, non_upper_case_globals)]
+    #![allow(non_snake_case, non_upper_case_globals, dead_code)]
 
+    use core::ffi::*;
+    use core::sync::atomic::{AtomicPtr, Ordering};
     use super::FnPtr;
-    use std::os::raw::*;
 
     macro_rules! store {
         ($name:ident) => {
-            pub(super) static mut $name: FnPtr = FnPtr { ptr: FnPtr::not_initialized as *const c_void, is_loaded: false };
+            pub(super) static $name: AtomicPtr<c_void> = AtomicPtr::new(core::ptr::null_mut());
+        }
+    }
+
+    #[inline]
+    pub(super) fn get(storage: &AtomicPtr<c_void>) -> FnPtr {
+        FnPtr::new(storage.load(Ordering::Relaxed))
+    }
+
+    pub(super) fn set(storage: &AtomicPtr<c_void>, ptr: *const c_void) {
+        storage.store(ptr as *mut c_void, Ordering::Relaxed);
+    }
+
+    /// Uses the pointer of `other` if `storage` is not loaded.
+    pub(super) fn alias(storage: &AtomicPtr<c_void>, other: &AtomicPtr<c_void>) {
+        if storage.load(Ordering::Relaxed).is_null() {
+            set(storage, other.load(Ordering::Relaxed));
         }
     }
 
@@ -111,6 +404,135 @@ mod storage {
 }
 {% endif %}
 
+{% if options.on_demand %}
+mod on_demand {
+    use core::cell::Cell;
+    use core::ffi::c_void;
+    use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+    use core::sync::atomic::{AtomicPtr, Ordering};
+    use super::FnPtr;
+
+    type LoadFn = Box<dyn FnMut(&'static str) -> *const c_void + Send>;
+
+    /// The loader is taken out while it is called, the lock is not held while it runs.
+    struct Loader {
+        loadfn: Option<LoadFn>,
+        /// incremented whenever the loader is replaced
+        generation: u64,
+        busy: bool,
+    }
+
+    static LOADER: Mutex<Loader> = Mutex::new(Loader { loadfn: None, generation: 0, busy: false });
+    static IDLE: Condvar = Condvar::new();
+
+    thread_local! {
+        static RESOLVING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    fn lock() -> MutexGuard<'static, Loader> {
+        LOADER.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Replaces the loader, functions which are being resolved through the previous loader are not stored.
+    pub fn replace(loadfn: Option<LoadFn>) {
+        let mut loader = lock();
+        loader.loadfn = loadfn;
+        loader.generation += 1;
+    }
+
+    fn aliases(name: &str) -> &'static [&'static str] {
+        match name {
+            {% for command, caliases in aliases|dictsort %}
+            "{{ command }}" => &[{{ caliases|reject('equalto', command)|map('tojson')|join(', ') }}],
+            {% endfor %}
+            _ => &[],
+        }
+    }
+
+    /// Gives the loader back once it returned or panicked.
+    struct Resolving {
+        loadfn: Option<LoadFn>,
+        generation: u64,
+    }
+
+    impl Drop for Resolving {
+        fn drop(&mut self) {
+            RESOLVING.with(|resolving| resolving.set(false));
+
+            let mut loader = lock();
+            if loader.generation == self.generation {
+                loader.loadfn = self.loadfn.take();
+            }
+            loader.busy = false;
+            IDLE.notify_all();
+        }
+    }
+
+    /// Resolves `name`, or one of its aliases, through the loader and caches the result.
+    ///
+    /// Returns null if the function can not be resolved, failures are not cached.
+    /// Functions called by the loader itself can not be resolved through it and return null.
+    #[cold]
+    #[inline(never)]
+    fn resolve(name: &'static str, storage: &AtomicPtr<c_void>) -> *const c_void {
+        if RESOLVING.with(Cell::get) {
+            return core::ptr::null();
+        }
+
+        let mut loader = lock();
+        while loader.busy {
+            loader = IDLE.wait(loader).unwrap_or_else(PoisonError::into_inner);
+        }
+
+        // another thread may have resolved the function while waiting for the lock
+        let cached = storage.load(Ordering::Relaxed);
+        if !cached.is_null() {
+            return cached;
+        }
+
+        let mut resolving = match loader.loadfn.take() {
+            Some(loadfn) => Resolving { loadfn: Some(loadfn), generation: loader.generation },
+            None => return core::ptr::null(),
+        };
+        loader.busy = true;
+        drop(loader);
+
+        RESOLVING.with(|resolving| resolving.set(true));
+        let loadfn = resolving.loadfn.as_mut().unwrap();
+        let ptr = core::iter::once(&name).chain(aliases(name))
+            .map(|&name| loadfn(name))
+            .find(|ptr| !ptr.is_null())
+            .unwrap_or(core::ptr::null());
+
+        // the loader might have been replaced while it was called
+        let loader = lock();
+        if loader.generation == resolving.generation {
+            storage.store(ptr as *mut c_void, Ordering::Relaxed);
+        }
+        drop(loader);
+
+        ptr
+    }
+
+    /// Function pointer to call, `FnPtr::not_initialized` if it can not be resolved.
+    #[inline]
+    pub fn ptr(name: &'static str, storage: &AtomicPtr<c_void>) -> *const c_void {
+        let ptr = storage.load(Ordering::Relaxed);
+        if !ptr.is_null() {
+            return ptr;
+        }
+
+        FnPtr::new(resolve(name, storage)).ptr
+    }
+
+    #[allow(dead_code)]
+    pub fn fn_ptr(name: &'static str, storage: &AtomicPtr<c_void>) -> FnPtr {
+        let ptr = storage.load(Ordering::Relaxed);
+        FnPtr::new(if ptr.is_null() { resolve(name, storage) } else { ptr })
+    }
+}
+{% endif %}
+
 {% if options.mx %}
 pub fn load<F>(mut loadfn: F) -> functions::{{ ctx_name }} where F: FnMut(&'static str) -> *const c_void {
     #[allow(unused_mut)]
@@ -118,29 +540,107 @@ pub fn load<F>(mut loadfn: F) -> functions::{{ ctx_name }} where F: FnMut(&'stat
         {% for command in feature_set.commands %}
         {{ template_utils.protect(command.name) }} {{ command.name|no_prefix }}: FnPtr::new(loadfn("{{ command.name }}")),
         {% endfor %}
+        {% if options.stats %}
+        stats: std::sync::Arc::new(stats::Counters::new()),
+        {% endif %}
+        {% if spec.name == 'gl' %}
+        version: (0, 0),
+        features: Features::default(),
+        extensions: Extensions::default(),
+        {% endif %}
     };
 
     {% for command, caliases in aliases|dictsort %}
     {% for alias in caliases|reject('equalto', command) %}
-    {{ template_utils.protect(command) }} ctx.{{ command|no_prefix }}.aliased(&ctx.{{ alias|no_prefix }});
+    {{ template_utils.protect(command) }}{{ template_utils.protect(alias) }} ctx.{{ command|no_prefix }}.aliased(&ctx.{{ alias|no_prefix }});
     {% endfor %}
     {% endfor %}
 
-     ctx
+    {% if spec.name == 'gl' %}
+    unsafe { load_features(&mut ctx) };
+    {% endif %}
+
+    ctx
+}
+{% elif options.on_demand %}
+/// Stores the loader, functions are resolved through it on their first call.
+pub fn load<F>(loadfn: F) where F: FnMut(&'static str) -> *const c_void + Send + 'static {
+    on_demand::replace(Some(Box::new(loadfn)));
+
+    // functions resolved through a previous loader might not be valid anymore
+    reset();
+
+    {% if spec.name == 'gl' %}
+    unsafe { load_features() };
+    {% endif %}
 }
 {% else %}
+/// Loads all commands through `loadfn`, can be called from any thread.
 pub fn load<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const c_void {
-    unsafe {
-        {% for command in feature_set.commands %}
-        {{ template_utils.protect(command) }} storage::{{ command.name | no_prefix }}.set_ptr(loadfn("{{ command.name }}"));
-        {% endfor %}
+    {% for command in feature_set.commands %}
+    {{ template_utils.protect(command) }} storage::set(&storage::{{ command.name | no_prefix }}, loadfn("{{ command.name }}"));
+    {% endfor %}
 
-        {% for command, caliases in aliases|dictsort %}
-        {% for alias in caliases|reject('equalto', command) %}
-        {{ template_utils.protect(command) }}{{ template_utils.protect(alias) }} storage::{{ command|no_prefix }}.aliased(&storage::{{ alias|no_prefix }});
-        {% endfor %}
-        {% endfor %}
-    }
+    {% for command, caliases in aliases|dictsort %}
+    {% for alias in caliases|reject('equalto', command) %}
+    {{ template_utils.protect(command) }}{{ template_utils.protect(alias) }} storage::alias(&storage::{{ command|no_prefix }}, &storage::{{ alias|no_prefix }});
+    {% endfor %}
+    {% endfor %}
+
+    {% if spec.name == 'gl' %}
+    unsafe { load_features() };
+    {% endif %}
+}
+{% endif %}
+{% if not options.mx %}
+
+/// Resets all commands to not loaded{{ ', the loader is dropped' if options.on_demand }}.
+pub fn unload() {
+    {% if options.on_demand %}
+    on_demand::replace(None);
+
+    {% endif %}
+    reset();
+    {% if spec.name == 'gl' %}
+    reset_features();
+    {% endif %}
+}
+
+fn reset() {
+    {% for command in feature_set.commands %}
+    {{ template_utils.protect(command) }} storage::set(&storage::{{ command.name|no_prefix }}, core::ptr::null());
+    {% endfor %}
 }
 {% endif %}
 
+{% include 'features/' + spec.name + '.rs' ignore missing %}
+
+{% include 'dispatch/' + spec.name + '.rs' ignore missing %}
+
+{% if options.safe %}
+{% include 'safe.rs' %}
+{% endif %}
+
+{% if options.mock %}
+{% include 'mock.rs' %}
+{% endif %}
+
+{% if options.trace %}
+{% include 'trace.rs' %}
+{% endif %}
+
+{% if options.stats %}
+{% include 'stats.rs' %}
+{% endif %}
+
+{% if options.tracing %}
+{% include 'instrument.rs' %}
+{% endif %}
+
+{% if options.objects %}
+{% include 'objects/' + spec.name + '.rs' ignore missing %}
+{% endif %}
+
+{% if options.loader %}
+{% include 'loader/' + spec.name + '.rs' ignore missing %}
+{% endif %}
@@ -0,0 +1,67 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Instrumentation of the commands through the `tracing` crate.
+///
+/// Every call enters a span at trace level named after the command, with the arguments
+/// and the return value as fields formatted with `Debug`.
+{% if spec.name == 'gl' %}
+/// With the `tracing-gl-errors` cargo feature, errors reported by `glGetError` after a call
+/// are emitted as events at error level, this consumes the error flag the application
+/// would otherwise query itself, but only while a subscriber is interested in the span.
+{% elif spec.name == 'vk' %}
+/// Failed calls, returning a negative `VkResult`, are emitted as events at error level.
+{% endif %}
+#[cfg(feature = "tracing")]
+mod instrument {
+    #![allow(non_snake_case, dead_code, unused_imports, unused_variables)]
+
+    use core::any::Any;
+    use core::ffi::*;
+    use core::fmt::Debug;
+    use ::tracing::field::Empty;
+    use super::types::*;
+    use super::FnPtr;
+
+    {% for command in feature_set.commands %}
+    {{ template_utils.protect(command) }} #[inline]
+    pub(super) fn {{ command.name|no_prefix }}({{ command|params }}) -> ::tracing::Span {
+        {% if command.params %}
+        // bound to a tuple, parameters may be named like the helpers imported by the macro
+        let args = ({{ command|params('names') }},);
+        {% endif %}
+        ::tracing::trace_span!("{{ command.name }}", {% for param in command.params %}{{ param.name|identifier }} = ?args.{{ loop.index0 }}, {% endfor %}ret = Empty)
+    }
+    {% endfor %}
+
+    #[inline]
+    pub(super) fn finish<T: Debug + 'static>(span: &::tracing::Span, name: &'static str, ret: &T{{ ', get_error: FnPtr' if spec.name == 'gl' and 'glGetError' in feature_set.commands }}) {
+        if span.is_disabled() {
+            return;
+        }
+
+        if (ret as &dyn Any).downcast_ref::<()>().is_none() {
+            span.record("ret", ::tracing::field::debug(ret));
+        }
+        {% if spec.name == 'vk' %}
+
+        if let Some(result) = (ret as &dyn Any).downcast_ref::<VkResult>() {
+            if {{ 'result.0' if options.newtype_enums else '(*result as i32)' }} < 0 {
+                ::tracing::error!(command = name, result = ?result, "{} failed", name);
+            }
+        }
+        {% elif spec.name == 'gl' and 'glGetError' in feature_set.commands %}
+
+        #[cfg(feature = "tracing-gl-errors")]
+        {
+            if name == "glGetError" || !get_error.is_loaded {
+                return;
+            }
+
+            const GL_NO_ERROR: GLenum = 0;
+            let error = unsafe { core::mem::transmute::<_, extern "system" fn() -> GLenum>(get_error.ptr)() };
+            if error != GL_NO_ERROR {
+                ::tracing::error!(command = name, error, "{} failed with error 0x{:04X}", name, error);
+            }
+        }
+        {% endif %}
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * Prints the size, alignment and field offsets of the types, as declared by the
+ * generated C headers, which are compared with the Rust types by `layout.rs`.
+ */
+#include <stddef.h>
+#include <stdio.h>
+
+#include <glad/{{ feature_set.name }}.h>
+
+#define ALIGN(type) _Alignof(type)
+
+#define LAYOUT(name, type) \
+    printf("size %s %lu\n", name, (unsigned long) sizeof(type)); \
+    printf("align %s %lu\n", name, (unsigned long) ALIGN(type))
+
+#define OFFSET(name, type, field) \
+    printf("offset %s.%s %lu\n", name, #field, (unsigned long) offsetof(type, field))
+
+int main(void) {
+{% for layout in layouts %}
+    LAYOUT("{{ layout.name }}", {{ layout.c_type }});
+{% for field, _ in layout.fields %}
+    OFFSET("{{ layout.name }}", {{ layout.c_type }}, {{ field }});
+{% endfor %}
+{% endfor %}
+    return 0;
+}
@@ -0,0 +1,76 @@
+//! Compares the layout of the types with the C headers.
+//!
+//! `layout/layout.c` is compiled against the generated C headers with the compiler
+//! from `CC`, `cc` by default, and prints the size, alignment and field offsets of every type.
+#![allow(non_snake_case)]
+{% if spec.name == 'wgl' %}
+#![cfg(windows)]
+{% endif %}
+
+extern crate glad_{{ feature_set.name|replace('-', '_') }};
+
+use std::collections::HashMap;
+use std::env;
+use std::mem::{align_of, size_of};
+use std::path::Path;
+use std::process::Command;
+
+use glad_{{ feature_set.name|replace('-', '_') }}::{{ spec.name }}::types::*;
+
+fn expected() -> HashMap<String, usize> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("layout");
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("layout");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&compiler)
+        .arg("-I").arg(dir.join("include"))
+        .arg(dir.join("layout.c"))
+        .arg("-o").arg(&program)
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run {}: {}", compiler, e));
+    assert!(status.success(), "failed to compile layout.c");
+
+    let output = Command::new(&program).output().expect("failed to run layout");
+    assert!(output.status.success(), "layout failed");
+
+    String::from_utf8(output.stdout).expect("output is not UTF-8").lines()
+        .map(|line| {
+            let (key, value) = line.rsplit_once(' ').expect("invalid output");
+            (key.to_string(), value.parse().expect("invalid output"))
+        })
+        .collect()
+}
+
+struct Layouts {
+    expected: HashMap<String, usize>,
+    mismatches: Vec<String>
+}
+
+impl Layouts {
+    fn check(&mut self, key: &str, actual: usize) {
+        match self.expected.get(key) {
+            Some(&expected) if expected == actual => {},
+            Some(&expected) => self.mismatches.push(format!("{}: {} in C, {} in Rust", key, expected, actual)),
+            None => self.mismatches.push(format!("{}: missing in C", key))
+        }
+    }
+}
+
+macro_rules! layout {
+    ($layouts:expr, $name:ident $(, $field:ident => $c:expr)*) => {
+        $layouts.check(concat!("size ", stringify!($name)), size_of::<$name>());
+        $layouts.check(concat!("align ", stringify!($name)), align_of::<$name>());
+        $($layouts.check(concat!("offset ", stringify!($name), ".", $c), ::std::mem::offset_of!($name, $field));)*
+    }
+}
+
+#[test]
+fn layout() {
+    let mut layouts = Layouts { expected: expected(), mismatches: Vec::new() };
+
+    {% for layout in layouts %}
+    {{ layout.cfg + ' ' if layout.cfg else '' }}layout!(layouts, {{ layout.name }}{% for field, rust_field in layout.fields %}, {{ rust_field }} => "{{ field }}"{% endfor %});
+    {% endfor %}
+
+    assert!(layouts.mismatches.is_empty(), "layouts differ:\n{}", layouts.mismatches.join("\n"));
+}
@@ -1,2 +1,14 @@
+{% if options.no_std %}
+#![no_std]
+
+{% else %}
+extern crate core;
+
+{% endif %}
+{% if options.tracing %}
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+{% endif %}
 #[allow(clippy::all)]
-pub mod {{ spec.name }};
\ No newline at end of file
+pub mod {{ spec.name }};
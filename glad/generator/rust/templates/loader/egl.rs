@@ -0,0 +1,26 @@
+{% include 'loader/library.rs' %}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const LIBRARY_NAMES: &[&str] = &["libEGL.dylib"];
+#[cfg(windows)]
+const LIBRARY_NAMES: &[&str] = &["libEGL.dll", "EGL.dll"];
+#[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+const LIBRARY_NAMES: &[&str] = &["libEGL.so.1", "libEGL.so"];
+
+/// Opens the system EGL library and loads all functions from it.
+///
+/// Functions exported by the library are preferred, everything else
+/// is resolved through `eglGetProcAddress`.
+pub fn load_from_system() -> Result<{{ 'functions::' + ctx_name if options.mx else '()' }}, LoaderError> {
+    let library = library::Library::open(LIBRARY_NAMES)?;
+
+    let get_proc_address = library.require("eglGetProcAddress")?;
+    let get_proc_address = unsafe {
+        std::mem::transmute::<_, extern "system" fn(*const std::os::raw::c_char) -> *const c_void>(get_proc_address)
+    };
+
+    Ok(load(move |name| {
+        let ptr = library.get(name);
+        if ptr.is_null() { library::with_c_str(name, |name| get_proc_address(name)) } else { ptr }
+    }))
+}
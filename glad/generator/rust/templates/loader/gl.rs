@@ -0,0 +1,61 @@
+{% include 'loader/library.rs' %}
+
+{% set library_names = {
+    'gl': {
+        'apple': ['/System/Library/Frameworks/OpenGL.framework/OpenGL', '/System/Library/Frameworks/OpenGL.framework/Versions/Current/OpenGL'],
+        'windows': ['opengl32.dll'],
+        'unix': ['libGL.so.1', 'libGL.so']
+    },
+    'gles1': {
+        'apple': ['libGLESv1_CM.dylib'],
+        'windows': ['GLESv1_CM.dll', 'libGLESv1_CM.dll', 'libGLES_CM.dll'],
+        'unix': ['libGLESv1_CM.so.1', 'libGLESv1_CM.so', 'libGLES_CM.so.1']
+    },
+    'gles2': {
+        'apple': ['libGLESv2.dylib'],
+        'windows': ['GLESv2.dll', 'libGLESv2.dll'],
+        'unix': ['libGLESv2.so.2', 'libGLESv2.so']
+    },
+    'glsc2': {
+        'apple': ['libGLESv2.dylib'],
+        'windows': ['GLESv2.dll', 'libGLESv2.dll'],
+        'unix': ['libGLESv2.so.2', 'libGLESv2.so']
+    }
+} %}
+{% macro names_for(platform) -%}
+&[{% for api in feature_set.info.apis %}{% set outer = loop %}{% for name in library_names[api][platform] %}"{{ name }}"{{ ', ' if not (loop.last and outer.last) }}{% endfor %}{% endfor %}]
+{%- endmacro %}
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const LIBRARY_NAMES: &[&str] = {{ names_for('apple') }};
+#[cfg(windows)]
+const LIBRARY_NAMES: &[&str] = {{ names_for('windows') }};
+#[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+const LIBRARY_NAMES: &[&str] = {{ names_for('unix') }};
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const GET_PROC_ADDRESS: &[&str] = &[];
+#[cfg(windows)]
+const GET_PROC_ADDRESS: &[&str] = &["wglGetProcAddress", "eglGetProcAddress"];
+#[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+const GET_PROC_ADDRESS: &[&str] = &["glXGetProcAddressARB", "eglGetProcAddress"];
+
+/// Opens the system {{ feature_set.info.apis|join('/') }} library and loads all functions from it.
+///
+/// Functions are resolved through the platform `GetProcAddress` function if
+/// the library exports one, and directly from the library otherwise.
+pub fn load_from_system() -> Result<{{ 'functions::' + ctx_name if options.mx else '()' }}, LoaderError> {
+    let library = library::Library::open(LIBRARY_NAMES)?;
+
+    let get_proc_address = GET_PROC_ADDRESS.iter()
+        .map(|name| library.get(name))
+        .find(|ptr| !ptr.is_null())
+        .map(|ptr| unsafe { std::mem::transmute::<_, extern "system" fn(*const std::os::raw::c_char) -> *const c_void>(ptr) });
+
+    Ok(load(move |name| {
+        let ptr = get_proc_address
+            .map(|get_proc_address| library::with_c_str(name, |name| get_proc_address(name)))
+            .unwrap_or(std::ptr::null());
+
+        if ptr.is_null() { library.get(name) } else { ptr }
+    }))
+}
@@ -0,0 +1,106 @@
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LoaderError {
+    /// None of the candidate libraries could be opened.
+    LibraryNotFound { names: &'static [&'static str] },
+    /// The library was opened but does not export a required entry point.
+    SymbolNotFound { library: &'static str, symbol: &'static str },
+}
+
+impl std::fmt::Display for LoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            LoaderError::LibraryNotFound { names } =>
+                write!(f, "{{ feature_set.name }}: unable to open any of {}", names.join(", ")),
+            LoaderError::SymbolNotFound { library, symbol } =>
+                write!(f, "{{ feature_set.name }}: {} does not export {}", library, symbol),
+        }
+    }
+}
+
+impl std::error::Error for LoaderError {}
+
+mod library {
+    use std::os::raw::{c_char, c_void};
+    use super::LoaderError;
+
+    #[cfg(not(windows))]
+    mod sys {
+        use std::os::raw::{c_char, c_int, c_void};
+
+        const RTLD_LAZY: c_int = 1;
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        const RTLD_LOCAL: c_int = 4;
+        #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+        const RTLD_LOCAL: c_int = 0;
+
+        extern "C" {
+            fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+            fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        }
+
+        pub unsafe fn open(name: *const c_char) -> *mut c_void { dlopen(name, RTLD_LAZY | RTLD_LOCAL) }
+        pub unsafe fn symbol(handle: *mut c_void, name: *const c_char) -> *mut c_void { dlsym(handle, name) }
+    }
+
+    #[cfg(windows)]
+    mod sys {
+        use std::os::raw::{c_char, c_void};
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn LoadLibraryA(name: *const c_char) -> *mut c_void;
+            fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+        }
+
+        pub unsafe fn open(name: *const c_char) -> *mut c_void { LoadLibraryA(name) }
+        pub unsafe fn symbol(handle: *mut c_void, name: *const c_char) -> *mut c_void { GetProcAddress(handle, name) }
+    }
+
+    /// A library opened by one of the builtin loaders.
+    ///
+    /// The library is intentionally never closed, loaded function pointers
+    /// stay valid for the lifetime of the process.
+    pub struct Library {
+        handle: *mut c_void,
+        pub name: &'static str,
+    }
+
+    // the library is never closed, the handle stays valid for the lifetime of the process
+    unsafe impl Send for Library {}
+    unsafe impl Sync for Library {}
+
+    impl Library {
+        pub fn open(names: &'static [&'static str]) -> Result<Library, LoaderError> {
+            for &name in names {
+                let handle = with_c_str(name, |name| unsafe { sys::open(name) });
+                if !handle.is_null() {
+                    return Ok(Library { handle, name });
+                }
+            }
+
+            Err(LoaderError::LibraryNotFound { names })
+        }
+
+        pub fn get(&self, symbol: &str) -> *const c_void {
+            with_c_str(symbol, |symbol| unsafe { sys::symbol(self.handle, symbol) as *const c_void })
+        }
+
+        #[allow(dead_code)]
+        pub fn require(&self, symbol: &'static str) -> Result<*const c_void, LoaderError> {
+            let ptr = self.get(symbol);
+            if ptr.is_null() {
+                return Err(LoaderError::SymbolNotFound { library: self.name, symbol });
+            }
+            Ok(ptr)
+        }
+    }
+
+    /// Calls `f` with a null terminated copy of `value`, names longer than 255 bytes are truncated.
+    pub fn with_c_str<F, R>(value: &str, f: F) -> R where F: FnOnce(*const c_char) -> R {
+        let mut buffer = [0 as c_char; 256];
+        for (dst, src) in buffer.iter_mut().zip(value.bytes().take(255)) {
+            *dst = src as c_char;
+        }
+        f(buffer.as_ptr())
+    }
+}
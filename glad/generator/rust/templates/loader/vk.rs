@@ -0,0 +1,69 @@
+{% include 'loader/library.rs' %}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const LIBRARY_NAMES: &[&str] = &["libvulkan.1.dylib"];
+#[cfg(windows)]
+const LIBRARY_NAMES: &[&str] = &["vulkan-1.dll", "vulkan.dll"];
+#[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+const LIBRARY_NAMES: &[&str] = &["libvulkan.so.1", "libvulkan.so"];
+
+/// `vkGetDeviceProcAddr` does not return NULL for non-device functions, see:
+/// https://github.com/KhronosGroup/Vulkan-LoaderAndValidationLayers/issues/2323
+fn is_device_command(name: &str) -> bool {
+    match name {
+        {% for command in device_commands %}
+        "{{ command.name }}" => true,
+        {% endfor %}
+        _ => false,
+    }
+}
+
+/// Opens the system Vulkan loader and loads all functions which are available
+/// without an instance, see `load_from_system_with`.
+pub fn load_from_system() -> Result<{{ 'functions::' + ctx_name if options.mx else '()' }}, LoaderError> {
+    load_from_system_with(None, None)
+}
+
+/// Opens the system Vulkan loader and loads all functions, instance and device
+/// functions are resolved through `vkGetInstanceProcAddr` and `vkGetDeviceProcAddr`
+/// if the respective handle is passed.
+pub fn load_from_system_with(instance: Option<types::VkInstance>, device: Option<types::VkDevice>)
+        -> Result<{{ 'functions::' + ctx_name if options.mx else '()' }}, LoaderError> {
+    type GetInstanceProcAddr = extern "system" fn(types::VkInstance, *const std::os::raw::c_char) -> *const c_void;
+    type GetDeviceProcAddr = extern "system" fn(types::VkDevice, *const std::os::raw::c_char) -> *const c_void;
+
+    let library = library::Library::open(LIBRARY_NAMES)?;
+
+    let get_instance_proc_addr = library.require("vkGetInstanceProcAddr")?;
+    let get_instance_proc_addr = unsafe { std::mem::transmute::<_, GetInstanceProcAddr>(get_instance_proc_addr) };
+    let get_device_proc_addr = library.require("vkGetDeviceProcAddr")?;
+    let get_device_proc_addr = unsafe { std::mem::transmute::<_, GetDeviceProcAddr>(get_device_proc_addr) };
+
+    Ok(load(move |name| {
+        let mut ptr = std::ptr::null();
+
+        if let Some(device) = device {
+            if is_device_command(name) {
+                ptr = library::with_c_str(name, |name| get_device_proc_addr(device, name));
+            }
+        }
+
+        if ptr.is_null() {
+            let instance = instance.unwrap_or_else(|| unsafe { std::mem::zeroed() });
+            ptr = library::with_c_str(name, |name| get_instance_proc_addr(instance, name));
+        }
+
+        if ptr.is_null() { library.get(name) } else { ptr }
+    }))
+}
+
+impl Entry {
+    /// Opens the system Vulkan loader and loads the global commands
+    /// through its `vkGetInstanceProcAddr`.
+    pub fn load_from_system() -> Result<Entry, LoaderError> {
+        let library = library::Library::open(LIBRARY_NAMES)?;
+        let get_instance_proc_addr = library.require("vkGetInstanceProcAddr")?;
+
+        Ok(unsafe { Entry::from_get_instance_proc_addr(get_instance_proc_addr) })
+    }
+}
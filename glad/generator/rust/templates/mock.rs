@@ -0,0 +1,183 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Mock implementations of the commands, to test code without a driver.
+///
+/// Every call of a mocked command is recorded with its arguments, a handler installed
+/// through `set` computes the result of a command, without one the result is zero.
+/// Commands looking up function pointers resolve to the mocks by default.
+///
+/// A panic of a handler, e.g. a failed assertion, is caught at the boundary of the
+/// mock and resumed once the command returned to the calling wrapper.
+///
+/// Handlers and recorded calls are global to the process and shared by all threads,
+/// tests using the mocks run in parallel by the test harness see each other's calls
+/// and handlers, they have to be serialized, e.g. through a lock held by every test
+/// or `--test-threads=1`.
+#[cfg(feature = "mock")]
+pub mod mock {
+    #![allow(non_snake_case, non_upper_case_globals, non_camel_case_types, unused_variables, dead_code, unused_imports)]
+    // the mocks mirror the signatures of the commands
+    #![allow(improper_ctypes_definitions)]
+
+    use core::any::Any;
+    use core::cell::Cell;
+    use core::ffi::*;
+    use core::fmt::Debug;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::{Arc, Mutex, MutexGuard};
+    use super::types::*;
+
+    /// A recorded call of a command.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Call {
+        pub name: &'static str,
+        /// Arguments formatted with `Debug`.
+        pub args: Vec<String>
+    }
+
+    static CALLS: Mutex<Vec<Call>> = Mutex::new(Vec::new());
+
+    std::thread_local! {
+        /// Panic of a handler, it can not unwind through the `extern "system"` mock.
+        static PANIC: Cell<Option<Box<dyn Any + Send>>> = const { Cell::new(None) };
+    }
+
+    /// Resumes the panic of a handler called by the last command of this thread.
+    #[inline]
+    pub(super) fn resume_unwind() {
+        if let Some(payload) = PANIC.with(|panic| panic.take()) {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        match mutex.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn record(name: &'static str, args: &[&dyn Debug]) {
+        let args = args.iter().map(|arg| format!("{:?}", arg)).collect();
+        lock(&CALLS).push(Call { name, args });
+    }
+
+    /// Calls recorded since the last `take_calls` or `reset`.
+    pub fn calls() -> Vec<Call> {
+        lock(&CALLS).clone()
+    }
+
+    /// Returns the recorded calls and clears them.
+    pub fn take_calls() -> Vec<Call> {
+        core::mem::take(&mut *lock(&CALLS))
+    }
+
+    /// Conversion of a resolved mock into the function pointer type returned by a command.
+    trait ProcAddr {
+        fn from_ptr(ptr: *const c_void) -> Self;
+    }
+
+    impl<T> ProcAddr for *mut T {
+        fn from_ptr(ptr: *const c_void) -> Self {
+            ptr as *mut T
+        }
+    }
+
+    impl ProcAddr for Option<unsafe extern "system" fn()> {
+        fn from_ptr(ptr: *const c_void) -> Self {
+            unsafe { core::mem::transmute::<*const c_void, Option<unsafe extern "system" fn()>>(ptr) }
+        }
+    }
+
+    unsafe fn proc_addr<T: ProcAddr>(name: *const c_char) -> T {
+        if name.is_null() {
+            return T::from_ptr(core::ptr::null());
+        }
+
+        T::from_ptr(loadfn(CStr::from_ptr(name).to_str().unwrap_or("")))
+    }
+
+    macro_rules! mock {
+        ($fun:ident, $cmd:expr, $default:expr, $ret:ty, $($name:ident: $typ:ty),*) => {
+            pub struct $fun {
+                handler: Mutex<Option<Arc<Mutex<dyn FnMut($($typ),*) -> $ret + Send>>>>
+            }
+
+            pub static $fun: $fun = $fun { handler: Mutex::new(None) };
+
+            impl $fun {
+                /// Installs `handler`, which is called with the arguments of every call.
+                ///
+                /// The handler may call other commands and install or remove handlers,
+                /// calling its own command from within deadlocks.
+                pub fn set<F>(&self, handler: F) where F: FnMut($($typ),*) -> $ret + Send + 'static {
+                    *lock(&self.handler) = Some(Arc::new(Mutex::new(handler)));
+                }
+
+                /// Removes the installed handler.
+                pub fn unset(&self) {
+                    *lock(&self.handler) = None;
+                }
+
+                extern "system" fn call($($name: $typ),*) -> $ret {
+                    record($cmd, &[$(&$name as &dyn Debug),*]);
+
+                    // not called under the lock of the slot, the handler may replace itself
+                    let handler = lock(&$fun.handler).clone();
+                    let handler = match handler {
+                        Some(handler) => handler,
+                        None => return unsafe { $default }
+                    };
+
+                    match panic::catch_unwind(AssertUnwindSafe(|| (&mut *lock(&handler))($($name),*))) {
+                        Ok(ret) => ret,
+                        Err(payload) => {
+                            PANIC.with(|panic| panic.set(Some(payload)));
+                            unsafe { $default }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    {% for command in feature_set.commands %}
+    {% if 'ProcAddr' in command.name %}
+    {% set default = 'proc_addr(' + command.params[-1].name|identifier + ' as *const c_char)' %}
+    {% else %}
+    {% set default = 'core::mem::zeroed()' %}
+    {% endif %}
+    {{ template_utils.protect(command) }} mock!({{ command.name|no_prefix }}, "{{ command.name }}", {{ default }}, {{ command.proto.ret|type }}, {{ command|params('full', 'super::types::') }});
+    {% endfor %}
+
+    /// Resolves the name of a command to its mock, null if there is none.
+    pub fn loadfn(name: &str) -> *const c_void {
+        match name {
+            {% for command in feature_set.commands %}
+            {{ template_utils.protect(command) }} "{{ command.name }}" => {{ command.name|no_prefix }}::call as *const c_void,
+            {% endfor %}
+            _ => core::ptr::null()
+        }
+    }
+
+    /// Loads the mocks of all commands.
+    pub fn load(){{ ' -> super::functions::' + ctx_name if options.mx }} {
+        {% if options.mx %}
+        let ctx = super::load(loadfn);
+        // loading calls commands without the wrappers
+        resume_unwind();
+        ctx
+        {% else %}
+        super::load(loadfn);
+        // loading calls commands without the wrappers
+        resume_unwind();
+        {% endif %}
+    }
+
+    /// Removes all installed handlers and clears the recorded calls.
+    pub fn reset() {
+        {% for command in feature_set.commands %}
+        {{ template_utils.protect(command) }} {{ command.name|no_prefix }}.unset();
+        {% endfor %}
+        lock(&CALLS).clear();
+    }
+}
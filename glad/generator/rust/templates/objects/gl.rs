@@ -0,0 +1,91 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Owned objects, which delete their name through the matching delete command when dropped.
+///
+/// Like the commands, objects must only be created and dropped while their context is current,
+/// dropping an object after the delete command was unloaded leaks the name.
+pub mod objects {
+    #![allow(unused_imports)]
+
+    use core::mem;
+    use super::types::*;
+    {% if options.mx %}
+    use super::functions::{{ ctx_name }};
+    {% endif %}
+
+    {% for object in objects %}
+    {% set gen_gate, delete_gate = template_utils.protect(object.gen), template_utils.protect(object.delete) %}
+    {% set gate = gen_gate if gen_gate == delete_gate else gen_gate + ' ' + delete_gate %}
+    {% set lifetime = "<'gl>" if options.mx %}
+    {% set ctx_param = ["gl: &'gl " + ctx_name] if options.mx else [] %}
+    {{ gate }}
+    /// Owned name created through `{{ object.gen.name }}`, deleted through `{{ object.delete.name }}` when dropped.
+    {% if not options.mx %}
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    {% endif %}
+    pub struct {{ object.name }}{{ lifetime }} {
+        {% if options.mx %}
+        gl: &'gl {{ ctx_name }},
+        {% endif %}
+        name: GLuint
+    }
+
+    {{ gate }}
+    impl{{ lifetime }} {{ object.name }}{{ lifetime }} {
+        {% if object.single %}
+        /// Creates a new object through `{{ object.gen.name }}`.
+        {% else %}
+        /// Generates a new name through `{{ object.gen.name }}`.
+        {% endif %}
+        pub unsafe fn new({{ (ctx_param + object.gen_params|map('join', ': ')|list)|join(', ') }}) -> {{ object.name }}{{ lifetime }} {
+            {% set args = object.gen_params|map('first')|list %}
+            {% if object.single %}
+            let name = {{ 'gl.' if options.mx else 'super::' }}{{ object.gen.name|no_prefix }}({{ args|join(', ') }});
+            {% else %}
+            let mut name = 0;
+            {{ 'gl.' if options.mx else 'super::' }}{{ object.gen.name|no_prefix }}({{ (args + ['1', '&mut name'])|join(', ') }});
+            {% endif %}
+            {{ object.name }} { {{ 'gl, ' if options.mx }}name }
+        }
+
+        {% if object.create %}
+        {{ template_utils.protect(object.create) }}
+        /// Creates a new, initialized object through `{{ object.create.name }}`.
+        pub unsafe fn create({{ (ctx_param + object.create_params|map('join', ': ')|list)|join(', ') }}) -> {{ object.name }}{{ lifetime }} {
+            let mut name = 0;
+            {{ 'gl.' if options.mx else 'super::' }}{{ object.create.name|no_prefix }}({{ (object.create_params|map('first')|list + ['1', '&mut name'])|join(', ') }});
+            {{ object.name }} { {{ 'gl, ' if options.mx }}name }
+        }
+
+        {% endif %}
+        /// Takes ownership of an existing name.
+        pub unsafe fn from_raw({{ "gl: &'gl " + ctx_name + ', ' if options.mx }}name: GLuint) -> {{ object.name }}{{ lifetime }} {
+            {{ object.name }} { {{ 'gl, ' if options.mx }}name }
+        }
+
+        /// The name of the object.
+        #[inline]
+        pub fn name(&self) -> GLuint {
+            self.name
+        }
+
+        /// Releases ownership of the name without deleting it.
+        pub fn into_raw(self) -> GLuint {
+            let name = self.name;
+            mem::forget(self);
+            name
+        }
+    }
+
+    {{ gate }}
+    impl{{ lifetime }} Drop for {{ object.name }}{{ lifetime }} {
+        fn drop(&mut self) {
+            {% if object.single %}
+            let _ = unsafe { {{ 'self.gl.try_' if options.mx else 'super::try_::' }}{{ object.delete.name|no_prefix }}(self.name) };
+            {% else %}
+            let _ = unsafe { {{ 'self.gl.try_' if options.mx else 'super::try_::' }}{{ object.delete.name|no_prefix }}(1, &self.name) };
+            {% endif %}
+        }
+    }
+
+    {% endfor %}
+}
@@ -0,0 +1,209 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Owned handles, destroyed through the matching destroy command when dropped.
+///
+/// Handles borrow their parent, the `Instance` or `Device` dispatch table or the pool they
+/// were allocated from, children can not outlive their parents and are always destroyed first.
+/// The allocation callbacks passed on creation are used to destroy the handle.
+pub mod objects {
+    #![allow(non_snake_case, unused_imports)]
+
+    use core::mem;
+    use core::ops::Deref;
+    use super::types::*;
+
+    fn allocation_callbacks(allocator: Option<&VkAllocationCallbacks>) -> *const VkAllocationCallbacks {
+        allocator.map_or(core::ptr::null(), |allocator| allocator)
+    }
+
+    /// Owned instance, destroyed through `vkDestroyInstance` when dropped.
+    {{ template_utils.gate('vkDestroyInstance') }}
+    pub struct Instance<'a> {
+        table: super::Instance,
+        allocator: Option<&'a VkAllocationCallbacks>
+    }
+
+    {{ template_utils.gate('vkDestroyInstance') }}
+    impl<'a> Instance<'a> {
+        /// Creates an instance through `vkCreateInstance` and loads its commands.
+        {{ template_utils.gate('vkCreateInstance') }} {{ template_utils.gate('vkGetInstanceProcAddr') }}
+        pub unsafe fn CreateInstance(entry: &super::Entry, pCreateInfo: &VkInstanceCreateInfo, allocator: Option<&'a VkAllocationCallbacks>) -> Result<Instance<'a>, VkResult> {
+            let mut handle = mem::zeroed();
+            let ret = entry.CreateInstance(pCreateInfo, allocation_callbacks(allocator), &mut handle);
+            if ret != VkResult::VK_SUCCESS {
+                return Err(ret);
+            }
+
+            Ok(Instance { table: entry.load_instance(handle), allocator })
+        }
+
+        /// Takes ownership of an instance created with `allocator`.
+        pub unsafe fn from_raw(table: super::Instance, allocator: Option<&'a VkAllocationCallbacks>) -> Instance<'a> {
+            Instance { table, allocator }
+        }
+
+        /// Releases ownership of the instance without destroying it.
+        pub fn into_raw(self) -> super::Instance {
+            let table = unsafe { core::ptr::read(&self.table) };
+            mem::forget(self);
+            table
+        }
+    }
+
+    {{ template_utils.gate('vkDestroyInstance') }}
+    impl<'a> Deref for Instance<'a> {
+        type Target = super::Instance;
+
+        fn deref(&self) -> &super::Instance {
+            &self.table
+        }
+    }
+
+    {{ template_utils.gate('vkDestroyInstance') }}
+    impl<'a> Drop for Instance<'a> {
+        fn drop(&mut self) {
+            unsafe { self.table.DestroyInstance(self.table.handle(), allocation_callbacks(self.allocator)) };
+        }
+    }
+
+    /// Owned device, destroyed through `vkDestroyDevice` when dropped.
+    {{ template_utils.gate('vkDestroyDevice') }}
+    pub struct Device<'a> {
+        instance: &'a super::Instance,
+        table: super::Device,
+        allocator: Option<&'a VkAllocationCallbacks>
+    }
+
+    {{ template_utils.gate('vkDestroyDevice') }}
+    impl<'a> Device<'a> {
+        /// Creates a device through `vkCreateDevice` and loads its commands.
+        {{ template_utils.gate('vkCreateDevice') }} {{ template_utils.gate('vkGetDeviceProcAddr') }}
+        pub unsafe fn CreateDevice(instance: &'a super::Instance, physicalDevice: VkPhysicalDevice, pCreateInfo: &VkDeviceCreateInfo, allocator: Option<&'a VkAllocationCallbacks>) -> Result<Device<'a>, VkResult> {
+            let mut handle = mem::zeroed();
+            let ret = instance.CreateDevice(physicalDevice, pCreateInfo, allocation_callbacks(allocator), &mut handle);
+            if ret != VkResult::VK_SUCCESS {
+                return Err(ret);
+            }
+
+            Ok(Device { instance, table: instance.load_device(handle), allocator })
+        }
+
+        /// Takes ownership of a device of `instance` created with `allocator`.
+        pub unsafe fn from_raw(instance: &'a super::Instance, table: super::Device, allocator: Option<&'a VkAllocationCallbacks>) -> Device<'a> {
+            Device { instance, table, allocator }
+        }
+
+        /// The instance the device was created from.
+        #[inline]
+        pub fn instance(&self) -> &'a super::Instance {
+            self.instance
+        }
+
+        /// Releases ownership of the device without destroying it.
+        pub fn into_raw(self) -> super::Device {
+            let table = unsafe { core::ptr::read(&self.table) };
+            mem::forget(self);
+            table
+        }
+    }
+
+    {{ template_utils.gate('vkDestroyDevice') }}
+    impl<'a> Deref for Device<'a> {
+        type Target = super::Device;
+
+        fn deref(&self) -> &super::Device {
+            &self.table
+        }
+    }
+
+    {{ template_utils.gate('vkDestroyDevice') }}
+    impl<'a> Drop for Device<'a> {
+        fn drop(&mut self) {
+            unsafe { self.table.DestroyDevice(self.table.handle(), allocation_callbacks(self.allocator)) };
+        }
+    }
+
+    {% for object in objects %}
+    {% set gate %}{% for command in object.gates %}{{ template_utils.protect(command) }} {% endfor %}{% endset %}
+    {% set has_parent_object = object.parent.handle is defined %}
+    {% set parent = object.parent.name + "<'a>" if has_parent_object else 'super::' + object.parent %}
+    {% set table = object.table|lower %}
+    {% set owns_allocator = 'allocator' in object.destroy_args %}
+    {{ gate }}
+    /// Owned `{{ object.handle }}`, destroyed through `{{ object.destroy.name }}` when dropped.
+    pub struct {{ object.name }}<'a> {
+        parent: &'a {{ parent }},
+        handle: {{ object.handle }},
+        {% if owns_allocator %}
+        allocator: Option<&'a VkAllocationCallbacks>
+        {% endif %}
+    }
+
+    {{ gate }}
+    impl<'a> {{ object.name }}<'a> {
+        {% for constructor in object.constructors %}
+        {{ template_utils.protect(constructor.command) }}
+        /// Creates a new handle through `{{ constructor.command.name }}`.
+        pub unsafe fn {{ constructor.command.name|no_prefix }}({{ (['parent: &\'a ' + parent] + constructor.params|map('join', ': ')|list + (["allocator: Option<&'a VkAllocationCallbacks>"] if constructor.allocator else []))|join(', ') }}) -> Result<{{ object.name }}<'a>, VkResult> {
+            {% if owns_allocator and not constructor.allocator %}
+            let allocator = None;
+            {% endif %}
+            let table = {{ 'parent.' + table + '()' if has_parent_object else 'parent' }};
+            let mut handle = mem::zeroed();
+            let ret = table.{{ constructor.command.name|no_prefix }}({{ constructor.args|join(', ') }});
+            if ret != VkResult::VK_SUCCESS {
+                return Err(ret);
+            }
+
+            Ok({{ object.name }} { parent, handle{{ ', allocator' if owns_allocator }} })
+        }
+
+        {% endfor %}
+        {% if owns_allocator %}
+        /// Takes ownership of a handle created with `allocator`.
+        pub unsafe fn from_raw(parent: &'a {{ parent }}, handle: {{ object.handle }}, allocator: Option<&'a VkAllocationCallbacks>) -> {{ object.name }}<'a> {
+            {{ object.name }} { parent, handle, allocator }
+        }
+        {% else %}
+        /// Takes ownership of a handle of `parent`.
+        pub unsafe fn from_raw(parent: &'a {{ parent }}, handle: {{ object.handle }}) -> {{ object.name }}<'a> {
+            {{ object.name }} { parent, handle }
+        }
+        {% endif %}
+
+        #[inline]
+        pub fn handle(&self) -> {{ object.handle }} {
+            self.handle
+        }
+
+        #[inline]
+        pub fn parent(&self) -> &'a {{ parent }} {
+            self.parent
+        }
+
+        /// The dispatch table the handle is destroyed through.
+        #[inline]
+        pub fn {{ table }}(&self) -> &'a super::{{ object.table }} {
+            self.parent{{ '.' + table + '()' if has_parent_object }}
+        }
+
+        /// Releases ownership of the handle without destroying it.
+        pub fn into_raw(self) -> {{ object.handle }} {
+            let handle = self.handle;
+            mem::forget(self);
+            handle
+        }
+    }
+
+    {{ gate }}
+    impl<'a> Drop for {{ object.name }}<'a> {
+        fn drop(&mut self) {
+            let table = self.{{ table }}();
+            {% if owns_allocator %}
+            let allocator = allocation_callbacks(self.allocator);
+            {% endif %}
+            let _ = unsafe { table.{{ object.destroy.name|no_prefix }}({{ object.destroy_args|join(', ') }}) };
+        }
+    }
+
+    {% endfor %}
+}
@@ -0,0 +1,143 @@
+//! Replays a trace recorded through `{{ spec.name }}::trace`, after loading
+{% if spec.name == 'gl' %}
+//! the commands in a headless context created through EGL.
+{% else %}
+//! the commands from the system library.
+{% endif %}
+//!
+//! Usage: `replay <trace>`
+extern crate glad_{{ feature_set.name|replace('-', '_') }};
+
+use glad_{{ feature_set.name|replace('-', '_') }}::{{ spec.name }};
+use std::fs::File;
+use std::io::BufReader;
+use std::process::exit;
+{% if spec.name == 'gl' %}
+
+/// Headless context the trace is replayed in, without a default framebuffer.
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
+mod context {
+    use std::ffi::CString;
+    use std::os::raw::{c_char, c_int, c_uint, c_void};
+    use std::ptr::null_mut;
+
+    const RTLD_LAZY: c_int = 1;
+    const EGL_NONE: c_int = 0x3038;
+    const EGL_PLATFORM_SURFACELESS_MESA: c_uint = 0x31DD;
+    {% if 'gl' in feature_set.info.apis %}
+    const EGL_API: c_uint = 0x30A2;
+    const ATTRIBS: &[c_int] = &[EGL_NONE];
+    {% else %}
+    const EGL_API: c_uint = 0x30A0;
+    const EGL_CONTEXT_MAJOR_VERSION: c_int = 0x3098;
+    const ATTRIBS: &[c_int] = &[EGL_CONTEXT_MAJOR_VERSION, {{ 1 if 'gles1' in feature_set.info.apis else 2 }}, EGL_NONE];
+    {% endif %}
+
+    type GetProcAddress = extern "system" fn(*const c_char) -> *mut c_void;
+    type GetPlatformDisplay = extern "system" fn(c_uint, *mut c_void, *const c_int) -> *mut c_void;
+    type GetDisplay = extern "system" fn(*mut c_void) -> *mut c_void;
+    type Initialize = extern "system" fn(*mut c_void, *mut c_int, *mut c_int) -> c_uint;
+    type BindAPI = extern "system" fn(c_uint) -> c_uint;
+    type CreateContext = extern "system" fn(*mut c_void, *mut c_void, *mut c_void, *const c_int) -> *mut c_void;
+    type MakeCurrent = extern "system" fn(*mut c_void, *mut c_void, *mut c_void, *mut c_void) -> c_uint;
+
+    extern "C" {
+        fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    }
+
+    unsafe fn symbol(library: *mut c_void, name: &str) -> Result<*mut c_void, String> {
+        let symbol = dlsym(library, CString::new(name).unwrap().as_ptr());
+        if symbol.is_null() {
+            return Err(format!("libEGL does not export {}", name));
+        }
+        Ok(symbol)
+    }
+
+    /// Creates a context without surfaces on the surfaceless platform of Mesa if it is
+    /// available and on the default display otherwise, makes it current and loads the commands.
+    pub unsafe fn create() -> Result<(), String> {
+        let library = ["libEGL.so.1", "libEGL.so"].iter()
+            .map(|name| dlopen(CString::new(*name).unwrap().as_ptr(), RTLD_LAZY))
+            .find(|library| !library.is_null())
+            .ok_or_else(|| "unable to open libEGL".to_string())?;
+
+        let get_proc_address: GetProcAddress = std::mem::transmute(symbol(library, "eglGetProcAddress")?);
+        let get_display: GetDisplay = std::mem::transmute(symbol(library, "eglGetDisplay")?);
+        let initialize: Initialize = std::mem::transmute(symbol(library, "eglInitialize")?);
+        let bind_api: BindAPI = std::mem::transmute(symbol(library, "eglBindAPI")?);
+        let create_context: CreateContext = std::mem::transmute(symbol(library, "eglCreateContext")?);
+        let make_current: MakeCurrent = std::mem::transmute(symbol(library, "eglMakeCurrent")?);
+
+        let get_platform_display = get_proc_address(CString::new("eglGetPlatformDisplayEXT").unwrap().as_ptr());
+        let display = if get_platform_display.is_null() {
+            get_display(null_mut())
+        } else {
+            let get_platform_display: GetPlatformDisplay = std::mem::transmute(get_platform_display);
+            get_platform_display(EGL_PLATFORM_SURFACELESS_MESA, null_mut(), [EGL_NONE].as_ptr())
+        };
+        if display.is_null() || initialize(display, null_mut(), null_mut()) == 0 {
+            return Err("unable to initialize an EGL display".to_string());
+        }
+
+        // contexts without a config and surface require EGL_KHR_no_config_context and EGL_KHR_surfaceless_context
+        let context = if bind_api(EGL_API) != 0 {
+            create_context(display, null_mut(), null_mut(), ATTRIBS.as_ptr())
+        } else {
+            null_mut()
+        };
+        if context.is_null() || make_current(display, null_mut(), null_mut(), context) == 0 {
+            return Err("unable to create a context without surfaces".to_string());
+        }
+
+        super::{{ spec.name }}::load(|name| {
+            let name = CString::new(name).unwrap();
+            get_proc_address(name.as_ptr()) as *const c_void
+        });
+        Ok(())
+    }
+}
+
+#[cfg(not(all(unix, not(any(target_os = "macos", target_os = "ios")))))]
+mod context {
+    pub unsafe fn create() -> Result<(), String> {
+        Err("contexts can only be created through EGL".to_string())
+    }
+}
+{% endif %}
+
+fn main() {
+    let path = match std::env::args_os().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: replay <trace>");
+            exit(2);
+        }
+    };
+
+    {% if spec.name == 'gl' %}
+    let loaded = unsafe { context::create() };
+    {% else %}
+    let loaded = {{ spec.name }}::load_from_system().map_err(|error| error.to_string());
+    {% endif %}
+    if let Err(error) = loaded {
+        eprintln!("{}", error);
+        exit(1);
+    }
+
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!("unable to open {}: {}", path.to_string_lossy(), error);
+            exit(1);
+        }
+    };
+
+    match unsafe { {{ spec.name }}::trace::replay(BufReader::new(file)) } {
+        Ok(summary) => println!("replayed {} calls, skipped {} calls", summary.replayed, summary.skipped),
+        Err(error) => {
+            eprintln!("unable to replay {}: {}", path.to_string_lossy(), error);
+            exit(1);
+        }
+    }
+}
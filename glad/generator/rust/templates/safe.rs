@@ -0,0 +1,58 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Wrappers taking slices, strings and references instead of raw pointers,
+/// lengths are derived from the slices and output parameters are returned.
+///
+/// The wrappers are still `unsafe`, the validity of the arguments
+/// (e.g. object names or enumerations) is not checked.
+pub mod safe {
+    #![allow(non_snake_case, unused_mut, unused_imports, clippy::all)]
+
+    use core::ffi::*;
+    use super::types::*;
+
+    {% for safe in safe_commands %}
+    {% set command = safe.command %}
+    {{ template_utils.protect(command) }}
+    #[inline]
+    pub unsafe fn {{ command.name|no_prefix }}{% if safe.generics %}<{{ safe.generics|join(', ') }}>{% endif %}({{ safe.params|join(', ') }}){% if safe.return_type %} -> {{ safe.return_type }}{% endif %} {
+        {% for statement in safe.pre %}
+        {{ statement }}
+        {% endfor %}
+        {% if safe.enumerate %}
+        {% set array, count, init = safe.enumerate %}
+        {% if safe.ret %}
+        let ret = loop {
+            let ret = super::{{ command.name|no_prefix }}({{ safe.count_args|join(', ') }});
+            if {{ 'ret.0' if options.newtype_enums else '(ret as i32)' }} < 0 || {{ count }} == 0 {
+                break ret;
+            }
+
+            {{ array }}.clear();
+            {{ array }}.resize_with({{ count }} as usize, || {{ init }});
+            let ret = super::{{ command.name|no_prefix }}({{ safe.args|join(', ') }});
+            if ret != VkResult::VK_INCOMPLETE {
+                break ret;
+            }
+        };
+        {% else %}
+        super::{{ command.name|no_prefix }}({{ safe.count_args|join(', ') }});
+        {{ array }}.resize_with({{ count }} as usize, || {{ init }});
+        super::{{ command.name|no_prefix }}({{ safe.args|join(', ') }});
+        {% endif %}
+        {{ array }}.truncate({{ count }} as usize);
+        {% elif safe.ret and not safe.outs %}
+        super::{{ command.name|no_prefix }}({{ safe.args|join(', ') }})
+        {% elif safe.ret %}
+        let ret = super::{{ command.name|no_prefix }}({{ safe.args|join(', ') }});
+        {% else %}
+        super::{{ command.name|no_prefix }}({{ safe.args|join(', ') }});
+        {% endif %}
+        {% for statement in safe.post %}
+        {{ statement }}
+        {% endfor %}
+        {% if safe.outs %}
+        {{ safe.return_value }}
+        {% endif %}
+    }
+    {% endfor %}
+}
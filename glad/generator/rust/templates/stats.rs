@@ -0,0 +1,217 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Number of calls and accumulated CPU time per command.
+pub mod stats {
+    #![allow(non_snake_case, dead_code)]
+
+    use core::fmt;
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    pub(super) struct Counter {
+        calls: AtomicU64,
+        nanos: AtomicU64
+    }
+
+    impl Counter {
+        const fn new() -> Counter {
+            Counter { calls: AtomicU64::new(0), nanos: AtomicU64::new(0) }
+        }
+
+        #[inline]
+        pub(super) fn record(&self, elapsed: Duration) {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        }
+
+        fn reset(&self) {
+            self.calls.store(0, Ordering::Relaxed);
+            self.nanos.store(0, Ordering::Relaxed);
+        }
+
+        fn report(&self, name: &'static str, report: &mut Vec<CommandStats>) {
+            let calls = self.calls.load(Ordering::Relaxed);
+            if calls > 0 {
+                let time = Duration::from_nanos(self.nanos.load(Ordering::Relaxed));
+                report.push(CommandStats { name, calls, time });
+            }
+        }
+    }
+
+    /// CPU time consumed by the calling thread.
+    #[cfg(any(
+        target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios",
+        target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"
+    ))]
+    #[inline]
+    pub(super) fn thread_time() -> Duration {
+        use core::ffi::{c_int, c_long};
+
+        #[repr(C)]
+        struct Timespec {
+            tv_sec: c_long,
+            tv_nsec: c_long
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        const CLOCK_THREAD_CPUTIME_ID: c_int = 3;
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        const CLOCK_THREAD_CPUTIME_ID: c_int = 16;
+        #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+        const CLOCK_THREAD_CPUTIME_ID: c_int = 14;
+        #[cfg(target_os = "netbsd")]
+        const CLOCK_THREAD_CPUTIME_ID: c_int = 0x20000000;
+        #[cfg(target_os = "openbsd")]
+        const CLOCK_THREAD_CPUTIME_ID: c_int = 4;
+
+        extern "C" {
+            fn clock_gettime(clock: c_int, time: *mut Timespec) -> c_int;
+        }
+
+        let mut time = Timespec { tv_sec: 0, tv_nsec: 0 };
+        if unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut time) } != 0 {
+            return Duration::ZERO;
+        }
+        Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+    }
+
+    /// CPU time consumed by the calling thread, in kernel and user mode.
+    #[cfg(windows)]
+    #[inline]
+    pub(super) fn thread_time() -> Duration {
+        use core::ffi::{c_int, c_void};
+
+        #[repr(C)]
+        #[derive(Default)]
+        struct Filetime {
+            low: u32,
+            high: u32
+        }
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetCurrentThread() -> *mut c_void;
+            fn GetThreadTimes(
+                thread: *mut c_void,
+                creation: *mut Filetime,
+                exit: *mut Filetime,
+                kernel: *mut Filetime,
+                user: *mut Filetime
+            ) -> c_int;
+        }
+
+        let (mut creation, mut exit) = (Filetime::default(), Filetime::default());
+        let (mut kernel, mut user) = (Filetime::default(), Filetime::default());
+        if unsafe { GetThreadTimes(GetCurrentThread(), &mut creation, &mut exit, &mut kernel, &mut user) } == 0 {
+            return Duration::ZERO;
+        }
+
+        // in units of 100 nanoseconds
+        let ticks = |time: Filetime| (time.high as u64) << 32 | time.low as u64;
+        Duration::from_nanos((ticks(kernel) + ticks(user)) * 100)
+    }
+
+    /// There is no clock for the CPU time of a thread, only calls are counted.
+    #[cfg(not(any(
+        windows,
+        target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios",
+        target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"
+    )))]
+    #[inline]
+    pub(super) fn thread_time() -> Duration {
+        Duration::ZERO
+    }
+
+    /// One counter per command, a context owns its own counters with `--mx`,
+    /// the global commands share a static instance.
+    pub(super) struct Counters {
+        {% for command in feature_set.commands %}
+        {{ template_utils.protect(command) }} pub(super) {{ command.name|no_prefix }}: Counter,
+        {% endfor %}
+    }
+
+    impl Counters {
+        pub(super) const fn new() -> Counters {
+            Counters {
+                {% for command in feature_set.commands %}
+                {{ template_utils.protect(command) }} {{ command.name|no_prefix }}: Counter::new(),
+                {% endfor %}
+            }
+        }
+
+        pub(super) fn snapshot(&self) -> Vec<CommandStats> {
+            let mut report = Vec::new();
+            {% for command in feature_set.commands %}
+            {{ template_utils.protect(command) }} self.{{ command.name|no_prefix }}.report("{{ command.name }}", &mut report);
+            {% endfor %}
+
+            report.sort_by(|a, b| b.time.cmp(&a.time).then(b.calls.cmp(&a.calls)));
+            report
+        }
+
+        pub(super) fn reset(&self) {
+            {% for command in feature_set.commands %}
+            {{ template_utils.protect(command) }} self.{{ command.name|no_prefix }}.reset();
+            {% endfor %}
+        }
+    }
+
+    /// Counters of the global commands{{ ' and the dispatch tables' if spec.name == 'vk' }}.
+    pub(super) static COUNTERS: Counters = Counters::new();
+
+    /// Statistics of a single command.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct CommandStats {
+        pub name: &'static str,
+        pub calls: u64,
+        /// CPU time the calling threads spent in the command, excludes time
+        /// they were blocked (e.g. by a driver synchronizing with the GPU).
+        pub time: Duration
+    }
+
+    impl fmt::Display for CommandStats {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}: {} calls, {:?}", self.name, self.calls, self.time)
+        }
+    }
+
+    {% if not options.mx %}
+    /// Statistics of all called commands, the most expensive first.
+    pub fn snapshot() -> Vec<CommandStats> {
+        COUNTERS.snapshot()
+    }
+
+    /// Resets the statistics of all commands.
+    pub fn reset() {
+        COUNTERS.reset()
+    }
+    {% elif options.mx_global %}
+    /// Statistics of all commands called through the current context, the most expensive first.
+    pub fn snapshot() -> Vec<CommandStats> {
+        super::current::with(|ctx| ctx.map(|ctx| ctx.stats_snapshot()).unwrap_or_default())
+    }
+
+    /// Resets the statistics of the current context.
+    pub fn reset() {
+        super::current::with(|ctx| {
+            if let Some(ctx) = ctx {
+                ctx.reset_stats();
+            }
+        })
+    }
+    {% endif %}
+}
+{% if options.mx %}
+
+impl functions::{{ ctx_name }} {
+    /// Statistics of all commands called through this context, the most expensive first,
+    /// clones of a context share their statistics.
+    pub fn stats_snapshot(&self) -> Vec<stats::CommandStats> {
+        self.stats.snapshot()
+    }
+
+    /// Resets the statistics of this context.
+    pub fn reset_stats(&self) {
+        self.stats.reset()
+    }
+}
+{% endif %}
@@ -1,6 +1,30 @@
 {% macro protect(symbol) %}
 {% set protections = spec.protections(symbol, feature_set=feature_set) %}
-{% if protections -%}
-#[cfg(any({{ protections|map('feature')|join(',') }}))]
+{% set protection = '#[cfg(any(' + protections|map('feature')|join(',') + '))]' if protections else '' %}
+{{ [protection, gate(symbol)|string]|select|join(' ') }}
+{%- endmacro %}
+
+{% macro gate(symbol) %}
+{% set features = feature_gates.get(symbol if symbol is string else symbol.name) %}
+{% if features -%}
+#[cfg(any({{ features|map('feature')|join(', ') }}))]
 {%- endif -%}
 {%- endmacro %}
+
+{# `ptr` if the command is enabled through its cargo features, otherwise a not loaded pointer #}
+{% macro gated_fn_ptr(name, ptr) -%}
+{% set features = feature_gates.get(name) %}
+{% if features -%}
+{ #[cfg(any({{ features|map('feature')|join(', ') }}))] let ptr = {{ ptr }}; #[cfg(not(any({{ features|map('feature')|join(', ') }})))] let ptr = FnPtr::new(core::ptr::null()); ptr }
+{%- else -%}
+{{ ptr }}
+{%- endif %}
+{%- endmacro %}
+
+{% macro fn_ptr(name) -%}
+{% if options.on_demand -%}
+on_demand::fn_ptr("{{ name }}", &storage::{{ name|no_prefix }})
+{%- else -%}
+storage::get(&storage::{{ name|no_prefix }})
+{%- endif %}
+{%- endmacro %}
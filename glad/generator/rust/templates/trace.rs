@@ -0,0 +1,595 @@
+{% import 'template_utils.rs' as template_utils with context %}
+/// Recording of all calls into a binary trace, which can be replayed later.
+///
+/// Arguments are recorded by value, payloads of pointers are recorded if their size
+/// is known from the specification{{ ' or, for attribute lists, up to `EGL_NONE`' if spec.name == 'egl' }}. When replaying, output pointers point to zeroed
+/// buffers of the recorded size{{ ' and pointers which may be offsets into buffer objects are passed with their recorded address' if spec.name == 'gl' }}.
+/// Calls passing callbacks or other pointers without a payload are not replayed{{ ', neither are
+/// calls passing native displays, windows or pixmaps other than null' if spec.name == 'egl' }}.
+///
+{% if spec.name in ('gl', 'egl', 'vk') %}
+{% set objects = 'object names' if spec.name == 'gl' else 'handles' %}
+/// The {{ objects }} a call returns or writes to output pointers are recorded after the call.
+/// When replaying, the {{ objects }} created by the replayed calls are passed in place of the
+/// recorded ones, other {{ objects }} (e.g. of objects created before recording started) as recorded.
+{% else %}
+/// Handles are passed as recorded, the calls are only replayed faithfully
+/// if they create the same objects.
+{% endif %}
+///
+/// Traces can only be replayed on the architecture they were recorded on.
+pub mod trace {
+    #![allow(non_snake_case, unused_variables, unused_mut, unused_imports, dead_code)]
+
+    use core::ffi::*;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use std::collections::{HashMap, VecDeque};
+    use std::fs::File;
+    use std::io::{self, BufWriter, Read, Write};
+    use std::path::Path;
+    use std::sync::{Mutex, MutexGuard};
+    use super::types::*;
+
+    const MAGIC: &[u8; 8] = b"GLADTRC1";
+    const API: &str = "{{ spec.name }}";
+    const COMMANDS: usize = {{ feature_set.commands|length }};
+    /// Size of the buffers passed for output pointers of unknown size.
+    const SCRATCH_SIZE: usize = 64 * 1024;
+
+    const RECORD_COMMAND: u8 = 0;
+    const RECORD_CALL: u8 = 1;
+    const RECORD_CREATED: u8 = 2;
+
+    const POINTER_NULL: u8 = 0;
+    const POINTER_ADDRESS: u8 = 1;
+    const POINTER_DATA: u8 = 2;
+    const POINTER_BUFFER: u8 = 3;
+    const POINTER_STRINGS: u8 = 4;
+
+    struct Recorder {
+        out: BufWriter<Box<dyn Write + Send>>,
+        /// Whether the name of a command has already been written.
+        written: Vec<bool>,
+        error: Option<io::Error>
+    }
+
+    static ACTIVE: AtomicBool = AtomicBool::new(false);
+    static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+    fn lock() -> MutexGuard<'static, Option<Recorder>> {
+        match RECORDER.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Starts recording all calls into a new file at `path`.
+    pub fn start<P: AsRef<Path>>(path: P) -> io::Result<()> {
+        start_writer(File::create(path)?)
+    }
+
+    /// Starts recording all calls into `writer`, a running recording is stopped.
+    pub fn start_writer<W: Write + Send + 'static>(writer: W) -> io::Result<()> {
+        stop()?;
+
+        let mut out = BufWriter::new(Box::new(writer) as Box<dyn Write + Send>);
+        out.write_all(MAGIC)?;
+        out.write_all(&[API.len() as u8])?;
+        out.write_all(API.as_bytes())?;
+
+        *lock() = Some(Recorder { out, written: vec![false; COMMANDS], error: None });
+        ACTIVE.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Stops recording and flushes the trace, returns the first error which occurred while recording.
+    pub fn stop() -> io::Result<()> {
+        ACTIVE.store(false, Ordering::Release);
+
+        match lock().take() {
+            Some(Recorder { error: Some(error), .. }) => Err(error),
+            Some(mut recorder) => recorder.out.flush(),
+            None => Ok(()),
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_active() -> bool {
+        ACTIVE.load(Ordering::Relaxed)
+    }
+
+    /// Writes a record of the command `id`, preceded by the name of the command the first time.
+    fn write(record: u8, id: usize, name: &'static str, payload: &[u8]) {
+        let mut guard = lock();
+        let recorder = match *guard {
+            Some(ref mut recorder) if recorder.error.is_none() => recorder,
+            _ => return,
+        };
+
+        let mut write = || -> io::Result<()> {
+            if !recorder.written[id] {
+                recorder.out.write_all(&[RECORD_COMMAND])?;
+                recorder.out.write_all(&(id as u32).to_le_bytes())?;
+                recorder.out.write_all(&[name.len() as u8])?;
+                recorder.out.write_all(name.as_bytes())?;
+                recorder.written[id] = true;
+            }
+
+            recorder.out.write_all(&[record])?;
+            recorder.out.write_all(&(id as u32).to_le_bytes())?;
+            recorder.out.write_all(&(payload.len() as u32).to_le_bytes())?;
+            recorder.out.write_all(payload)
+        };
+
+        if let Err(error) = write() {
+            recorder.error = Some(error);
+        }
+    }
+
+    /// Object names and handles of any size as key of the objects created when replaying.
+    unsafe fn key(ptr: *const u8, size: usize) -> u64 {
+        let mut key = 0u64;
+        core::ptr::copy_nonoverlapping(ptr, &mut key as *mut u64 as *mut u8, size.min(8));
+        key
+    }
+
+    /// Number of elements of an attribute list, including the terminating key.
+    unsafe fn attrib_list_len<T: Copy + PartialEq>(ptr: *const T, none: T) -> i64 {
+        if ptr.is_null() {
+            return 0;
+        }
+
+        let mut len = 0;
+        while *ptr.add(len) != none {
+            len += 2;
+        }
+        len as i64 + 1
+    }
+
+    /// Arguments of a single call, written as one record.
+    struct Call {
+        args: Vec<u8>
+    }
+
+    impl Call {
+        fn new() -> Call {
+            Call { args: Vec::new() }
+        }
+
+        fn value<T>(&mut self, value: &T) {
+            let bytes = unsafe { core::slice::from_raw_parts(value as *const T as *const u8, core::mem::size_of::<T>()) };
+            self.args.extend_from_slice(bytes);
+        }
+
+        fn data(&mut self, bytes: &[u8]) {
+            self.args.push(POINTER_DATA);
+            self.args.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            self.args.extend_from_slice(bytes);
+        }
+
+        fn address<T>(&mut self, ptr: *const T) {
+            if ptr.is_null() {
+                self.args.push(POINTER_NULL);
+            } else {
+                self.args.push(POINTER_ADDRESS);
+                self.args.extend_from_slice(&(ptr as usize as u64).to_le_bytes());
+            }
+        }
+
+        unsafe fn string(&mut self, ptr: *const c_char) {
+            if ptr.is_null() {
+                self.args.push(POINTER_NULL);
+            } else {
+                self.data(CStr::from_ptr(ptr).to_bytes_with_nul());
+            }
+        }
+
+        unsafe fn input<T>(&mut self, ptr: *const T, count: i64) {
+            if ptr.is_null() {
+                self.args.push(POINTER_NULL);
+            } else {
+                let size = count.max(0) as usize * core::mem::size_of::<T>();
+                self.data(core::slice::from_raw_parts(ptr as *const u8, size));
+            }
+        }
+
+        unsafe fn strings<T, L: Copy + Into<i64>>(&mut self, ptr: *const *const T, count: i64, lengths: *const L) {
+            if ptr.is_null() {
+                self.args.push(POINTER_NULL);
+                return;
+            }
+
+            let count = count.max(0) as usize;
+            self.args.push(POINTER_STRINGS);
+            self.args.extend_from_slice(&(count as u32).to_le_bytes());
+            for i in 0..count {
+                let string = *ptr.add(i) as *const c_char;
+                let length = if lengths.is_null() { -1 } else { (*lengths.add(i)).into() };
+
+                let bytes = if string.is_null() {
+                    &[][..]
+                } else if length < 0 {
+                    CStr::from_ptr(string).to_bytes()
+                } else {
+                    core::slice::from_raw_parts(string as *const u8, length as usize)
+                };
+
+                // strings are null-terminated when replaying
+                self.args.extend_from_slice(&(bytes.len() as u32 + 1).to_le_bytes());
+                self.args.extend_from_slice(bytes);
+                self.args.push(0);
+            }
+        }
+
+        fn output<T>(&mut self, ptr: *mut T, count: Option<i64>) {
+            if ptr.is_null() {
+                self.args.push(POINTER_NULL);
+            } else {
+                // zero for an unknown size
+                let size = count.map_or(0, |count| count.max(0) as usize * core::mem::size_of::<T>());
+                self.args.push(POINTER_BUFFER);
+                self.args.extend_from_slice(&(size as u32).to_le_bytes());
+            }
+        }
+
+        fn finish(self, id: usize, name: &'static str) {
+            write(RECORD_CALL, id, name, &self.args);
+        }
+    }
+
+    /// Objects a call creates, written after the call returned: the returned
+    /// object followed by the objects written to the output pointers.
+    pub(super) struct Created {
+        id: usize,
+        name: &'static str,
+        ret: bool,
+        /// Output pointers with the size and number of their objects.
+        outputs: Vec<(*const u8, usize, usize)>
+    }
+
+    impl Created {
+        fn new(id: usize, name: &'static str, ret: bool) -> Created {
+            Created { id, name, ret, outputs: Vec::new() }
+        }
+
+        fn output<T>(&mut self, ptr: *mut T, count: i64) {
+            if !ptr.is_null() {
+                self.outputs.push((ptr as *const u8, core::mem::size_of::<T>(), count.max(0) as usize));
+            }
+        }
+
+        pub(super) unsafe fn finish<T>(self, ret: &T) {
+            let mut objects = Vec::new();
+            if self.ret {
+                objects.extend_from_slice(&key(ret as *const T as *const u8, core::mem::size_of::<T>()).to_le_bytes());
+            }
+            for &(ptr, size, count) in &self.outputs {
+                for i in 0..count {
+                    objects.extend_from_slice(&key(ptr.add(i * size), size).to_le_bytes());
+                }
+            }
+
+            write(RECORD_CREATED, self.id, self.name, &objects);
+        }
+    }
+
+    {% for traced in traced_commands %}
+    {% set command = traced.command %}
+    {{ template_utils.protect(command) }} #[cold]
+    pub(super) unsafe fn {{ command.name|no_prefix }}({{ command|params }}) -> Option<Created> {
+        let mut call = Call::new();
+        {% for param in traced.params %}
+        {% if param.kind == 'value' %}
+        call.value(&{{ param.name }});
+        {% elif param.kind == 'string' %}
+        call.string({{ param.name }} as *const c_char);
+        {% elif param.kind == 'input' %}
+        call.input({{ param.name }}, {{ param.count }});
+        {% elif param.kind == 'strings' %}
+        call.strings({{ param.name }}, {{ param.count }}, {{ param.lengths or 'core::ptr::null::<i32>()' }});
+        {% elif param.kind == 'output' %}
+        call.output({{ param.name }}, {{ 'Some({})'.format(param.count) if param.count else 'None' }});
+        {% else %}
+        call.address({{ param.name }});
+        {% endif %}
+        {% endfor %}
+        call.finish({{ loop.index0 }}, "{{ command.name }}");
+        {% if traced.creates %}
+
+        let {{ 'mut ' if traced.created }}created = Created::new({{ loop.index0 }}, "{{ command.name }}", {{ 'true' if traced.ret_object else 'false' }});
+        {% for param in traced.created %}
+        created.output({{ param.name }}, {{ param.count }});
+        {% endfor %}
+        Some(created)
+        {% else %}
+        None
+        {% endif %}
+    }
+    {% endfor %}
+
+    /// Number of replayed and skipped calls.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Summary {
+        pub replayed: usize,
+        /// Calls of commands which are not available or can not be replayed.
+        pub skipped: usize
+    }
+
+    fn invalid(message: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    /// Reader of the arguments of a recorded call.
+    struct Args<'a> {
+        bytes: &'a [u8],
+        /// Buffers the pointer arguments point to, aligned for every element type.
+        buffers: Vec<Vec<u64>>
+    }
+
+    impl<'a> Args<'a> {
+        fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+            if self.bytes.len() < len {
+                return Err(invalid("truncated call"));
+            }
+
+            let (head, tail) = self.bytes.split_at(len);
+            self.bytes = tail;
+            Ok(head)
+        }
+
+        fn u32(&mut self) -> io::Result<u32> {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(self.take(4)?);
+            Ok(u32::from_le_bytes(bytes))
+        }
+
+        unsafe fn value<T>(&mut self) -> io::Result<T> {
+            let bytes = self.take(core::mem::size_of::<T>())?;
+            Ok(core::ptr::read_unaligned(bytes.as_ptr() as *const T))
+        }
+
+        fn buffer(&mut self, size: usize) -> *mut u8 {
+            let mut buffer = vec![0u64; (size + 7) / 8];
+            let ptr = buffer.as_mut_ptr() as *mut u8;
+            self.buffers.push(buffer);
+            ptr
+        }
+
+        fn pointer(&mut self) -> io::Result<*mut u8> {
+            self.sized_pointer().map(|(ptr, _)| ptr)
+        }
+
+        /// Pointer and the size of the data or buffer it points to.
+        fn sized_pointer(&mut self) -> io::Result<(*mut u8, usize)> {
+            match self.take(1)?[0] {
+                POINTER_NULL => Ok((core::ptr::null_mut(), 0)),
+                POINTER_ADDRESS => {
+                    let mut bytes = [0; 8];
+                    bytes.copy_from_slice(self.take(8)?);
+                    Ok((u64::from_le_bytes(bytes) as usize as *mut u8, 0))
+                }
+                POINTER_DATA => {
+                    let len = self.u32()? as usize;
+                    let data = self.take(len)?;
+                    let ptr = self.buffer(len);
+                    unsafe { core::ptr::copy_nonoverlapping(data.as_ptr(), ptr, len) };
+                    Ok((ptr, len))
+                }
+                POINTER_BUFFER => {
+                    let size = self.u32()? as usize;
+                    Ok((self.buffer(if size == 0 { SCRATCH_SIZE } else { size }), size))
+                }
+                POINTER_STRINGS => {
+                    let count = self.u32()? as usize;
+                    let strings = self.buffer(count * core::mem::size_of::<*const u8>()) as *mut *const u8;
+                    for i in 0..count {
+                        let len = self.u32()? as usize;
+                        let data = self.take(len)?;
+                        let string = self.buffer(len);
+                        unsafe {
+                            core::ptr::copy_nonoverlapping(data.as_ptr(), string, len);
+                            *strings.add(i) = string;
+                        }
+                    }
+                    Ok((strings as *mut u8, count * core::mem::size_of::<*const u8>()))
+                }
+                _ => Err(invalid("invalid pointer argument")),
+            }
+        }
+
+        /// Pointer to an array of `T` and its number of elements.
+        fn array<T>(&mut self) -> io::Result<(*mut u8, usize)> {
+            let (ptr, size) = self.sized_pointer()?;
+            Ok((ptr, size / core::mem::size_of::<T>()))
+        }
+    }
+
+    /// Outcome of replaying a call.
+    enum Replayed {
+        Called,
+        /// The kinds and keys of the objects the call created.
+        Created(Vec<(&'static str, u64)>),
+        /// The call was passed native handles, these are only valid in the recording process.
+        Skipped
+    }
+
+    /// Objects created by the replayed calls, by the kind of the object and the recorded object.
+    #[derive(Default)]
+    struct Objects {
+        replayed: HashMap<(&'static str, u64), u64>,
+        /// Objects created by a replayed call per command, until the objects of the recorded call are read.
+        pending: HashMap<u32, VecDeque<Vec<(&'static str, u64)>>>
+    }
+
+    impl Objects {
+        /// The object created in place of the recorded `value`, `value` if there is none.
+        fn map<T>(&self, kind: &'static str, value: T) -> T {
+            let key = unsafe { key(&value as *const T as *const u8, core::mem::size_of::<T>()) };
+            match self.replayed.get(&(kind, key)) {
+                Some(replayed) => unsafe { core::ptr::read(replayed as *const u64 as *const T) },
+                None => value,
+            }
+        }
+
+        unsafe fn map_array<T>(&self, kind: &'static str, (ptr, len): (*mut u8, usize)) {
+            let ptr = ptr as *mut T;
+            for i in 0..len {
+                ptr.add(i).write(self.map(kind, ptr.add(i).read()));
+            }
+        }
+
+        fn record(&mut self, id: u32, created: Vec<(&'static str, u64)>) {
+            self.pending.entry(id).or_default().push_back(created);
+        }
+
+        /// Maps the objects recorded after the call of command `id` to the objects created when replaying it.
+        fn created(&mut self, id: u32, recorded: &[u8]) {
+            let created = match self.pending.get_mut(&id).and_then(|pending| pending.pop_front()) {
+                Some(created) => created,
+                // the call was skipped
+                None => return,
+            };
+
+            for (bytes, (kind, replayed)) in recorded.chunks_exact(8).zip(created) {
+                let mut key = [0; 8];
+                key.copy_from_slice(bytes);
+                self.replayed.insert((kind, u64::from_le_bytes(key)), replayed);
+            }
+        }
+    }
+
+    unsafe fn push_created<T>(created: &mut Vec<(&'static str, u64)>, kind: &'static str, (ptr, len): (*mut u8, usize)) {
+        for i in 0..len {
+            created.push((kind, key(ptr.add(i * core::mem::size_of::<T>()), core::mem::size_of::<T>())));
+        }
+    }
+
+    fn is_null<T>(value: &T) -> bool {
+        unsafe { key(value as *const T as *const u8, core::mem::size_of::<T>()) == 0 }
+    }
+
+    type Replay = unsafe fn(&mut Args, &Objects) -> io::Result<Replayed>;
+
+    mod replay {
+        use super::*;
+        use super::super::functions;
+
+        {% for traced in traced_commands if traced.replayable %}
+        {% set command = traced.command %}
+        {{ template_utils.protect(command) }}
+        pub(super) unsafe fn {{ command.name|no_prefix }}(args: &mut Args, objects: &Objects) -> io::Result<Replayed> {
+            {% for param in traced.params %}
+            {% if param.kind == 'value' and param.object %}
+            let {{ param.name }} = objects.map("{{ param.object }}", args.value()?);
+            {% elif param.kind == 'value' %}
+            let {{ param.name }} = args.value()?;
+            {% elif param.object %}
+            let {{ param.name }} = args.array::<{{ param.element }}>()?;
+            {% if param.kind == 'input' %}
+            objects.map_array::<{{ param.element }}>("{{ param.object }}", {{ param.name }});
+            {% endif %}
+            {% else %}
+            let {{ param.name }} = args.pointer()?;
+            {% endif %}
+            {% endfor %}
+            {% for param in traced.params if param.native %}
+            if !is_null(&{{ param.name }}) {
+                return Ok(Replayed::Skipped);
+            }
+            {% endfor %}
+            {{ 'let ret = ' if traced.ret_object }}functions::{{ command.name|no_prefix }}({% for param in traced.params %}{{ param.name }}{{ '.0' if param.object and param.kind != 'value' }}{{ ' as _' if param.kind != 'value' }}{{ ', ' if not loop.last }}{% endfor %});
+            {% if traced.creates %}
+
+            let mut created = Vec::new();
+            {% if traced.ret_object %}
+            created.push(("{{ traced.ret_object }}", key(&ret as *const _ as *const u8, core::mem::size_of_val(&ret))));
+            {% endif %}
+            {% for param in traced.created %}
+            push_created::<{{ param.element }}>(&mut created, "{{ param.object }}", {{ param.name }});
+            {% endfor %}
+            Ok(Replayed::Created(created))
+            {% else %}
+            Ok(Replayed::Called)
+            {% endif %}
+        }
+        {% endfor %}
+    }
+
+    fn replayer(name: &str) -> Option<Replay> {
+        match name {
+            {% for traced in traced_commands if traced.replayable %}
+            {{ template_utils.protect(traced.command) }} "{{ traced.command.name }}" => Some(replay::{{ traced.command.name|no_prefix }}),
+            {% endfor %}
+            _ => None
+        }
+    }
+
+    fn read_exact<R: Read>(reader: &mut R, len: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0; len];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+        let mut bytes = [0; 4];
+        reader.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    /// Replays a recorded trace by calling the loaded commands{{ ', a context has to be current' if spec.name == 'gl' }}.
+    ///
+    /// Calls of commands which are not available or can not be replayed are skipped,
+    /// calls of commands which are not loaded panic like every other call.
+    pub unsafe fn replay<R: Read>(mut reader: R) -> io::Result<Summary> {
+        if &read_exact(&mut reader, MAGIC.len())?[..] != MAGIC {
+            return Err(invalid("not a trace"));
+        }
+        let len = read_exact(&mut reader, 1)?[0] as usize;
+        if read_exact(&mut reader, len)? != API.as_bytes() {
+            return Err(invalid("trace of a different API"));
+        }
+
+        let mut commands: HashMap<u32, Option<Replay>> = HashMap::new();
+        let mut objects = Objects::default();
+        let mut summary = Summary::default();
+        loop {
+            let mut record = [0];
+            if reader.read(&mut record)? == 0 {
+                return Ok(summary);
+            }
+
+            let id = read_u32(&mut reader)?;
+            match record[0] {
+                RECORD_COMMAND => {
+                    let len = read_exact(&mut reader, 1)?[0] as usize;
+                    let name = read_exact(&mut reader, len)?;
+                    let name = core::str::from_utf8(&name).map_err(|_| invalid("invalid command name"))?;
+                    commands.insert(id, replayer(name));
+                }
+                RECORD_CALL => {
+                    let len = read_u32(&mut reader)? as usize;
+                    let bytes = read_exact(&mut reader, len)?;
+
+                    match commands.get(&id) {
+                        Some(&Some(replay)) => match replay(&mut Args { bytes: &bytes, buffers: Vec::new() }, &objects)? {
+                            Replayed::Called => summary.replayed += 1,
+                            Replayed::Created(created) => {
+                                objects.record(id, created);
+                                summary.replayed += 1;
+                            }
+                            Replayed::Skipped => summary.skipped += 1,
+                        },
+                        Some(&None) => summary.skipped += 1,
+                        None => return Err(invalid("call of an unknown command")),
+                    }
+                }
+                RECORD_CREATED => {
+                    let len = read_u32(&mut reader)? as usize;
+                    let bytes = read_exact(&mut reader, len)?;
+                    objects.created(id, &bytes);
+                }
+                _ => return Err(invalid("invalid record")),
+            }
+        }
+    }
+}
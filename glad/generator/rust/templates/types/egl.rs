@@ -5,36 +5,36 @@ pub type EGLint = khronos_int32_t;
 
 // TODO replace based on platform, see eglplatform.h
 #[cfg(target_os = "macos")]      pub type EGLNativeDisplayType = i32;
-#[cfg(not(target_os = "macos"))] pub type EGLNativeDisplayType = *mut std::os::raw::c_void;
-pub type EGLNativeWindowType = *mut std::os::raw::c_void;
-pub type EGLNativePixmapType = *mut std::os::raw::c_void;
+#[cfg(not(target_os = "macos"))] pub type EGLNativeDisplayType = *mut core::ffi::c_void;
+pub type EGLNativeWindowType = *mut core::ffi::c_void;
+pub type EGLNativePixmapType = *mut core::ffi::c_void;
 
 // EGL types
-pub type EGLBoolean = std::os::raw::c_uint;
-pub type EGLenum = std::os::raw::c_uint;
+pub type EGLBoolean = core::ffi::c_uint;
+pub type EGLenum = core::ffi::c_uint;
 
-pub type EGLClientBuffer = *mut std::os::raw::c_void;
-pub type EGLConfig = *mut std::os::raw::c_void;
-pub type EGLContext = *mut std::os::raw::c_void;
-pub type EGLDeviceEXT = *mut std::os::raw::c_void;
-pub type EGLDisplay = *mut std::os::raw::c_void;
-pub type EGLImage = *mut std::os::raw::c_void;
-pub type EGLImageKHR = *mut std::os::raw::c_void;
-pub type EGLLabelKHR = *mut std::os::raw::c_void;
-pub type EGLObjectKHR = *mut std::os::raw::c_void;
-pub type EGLOutputLayerEXT = *mut std::os::raw::c_void;
-pub type EGLOutputPortEXT = *mut std::os::raw::c_void;
-pub type EGLStreamKHR = *mut std::os::raw::c_void;
-pub type EGLSurface = *mut std::os::raw::c_void;
-pub type EGLSync = *mut std::os::raw::c_void;
-pub type EGLSyncKHR = *mut std::os::raw::c_void;
-pub type EGLSyncNV = *mut std::os::raw::c_void;
+pub type EGLClientBuffer = *mut core::ffi::c_void;
+pub type EGLConfig = *mut core::ffi::c_void;
+pub type EGLContext = *mut core::ffi::c_void;
+pub type EGLDeviceEXT = *mut core::ffi::c_void;
+pub type EGLDisplay = *mut core::ffi::c_void;
+pub type EGLImage = *mut core::ffi::c_void;
+pub type EGLImageKHR = *mut core::ffi::c_void;
+pub type EGLLabelKHR = *mut core::ffi::c_void;
+pub type EGLObjectKHR = *mut core::ffi::c_void;
+pub type EGLOutputLayerEXT = *mut core::ffi::c_void;
+pub type EGLOutputPortEXT = *mut core::ffi::c_void;
+pub type EGLStreamKHR = *mut core::ffi::c_void;
+pub type EGLSurface = *mut core::ffi::c_void;
+pub type EGLSync = *mut core::ffi::c_void;
+pub type EGLSyncKHR = *mut core::ffi::c_void;
+pub type EGLSyncNV = *mut core::ffi::c_void;
 
 pub type EGLAttrib = isize;
 pub type EGLAttribKHR = isize;
 pub enum __eglMustCastToProperFunctionPointerType_fn {}
 pub type __eglMustCastToProperFunctionPointerType = *mut __eglMustCastToProperFunctionPointerType_fn;
-pub type EGLNativeFileDescriptorKHR = std::os::raw::c_int;
+pub type EGLNativeFileDescriptorKHR = core::ffi::c_int;
 pub type EGLnsecsANDROID = khronos_stime_nanoseconds_t;
 pub type EGLsizeiANDROID = khronos_ssize_t;
 pub type EGLTimeKHR = khronos_utime_nanoseconds_t;
@@ -44,39 +44,39 @@ pub type EGLuint64KHR = khronos_uint64_t;
 pub type EGLuint64NV = khronos_utime_nanoseconds_t;
 pub struct AHardwareBuffer;
 
-pub type EGLSetBlobFuncANDROID = extern "system" fn (
-    *const std::os::raw::c_void,
+pub type EGLSetBlobFuncANDROID = Option<unsafe extern "system" fn (
+    *const core::ffi::c_void,
     EGLsizeiANDROID,
-    *const std::os::raw::c_void,
+    *const core::ffi::c_void,
     EGLsizeiANDROID
-) -> ();
-pub type EGLGetBlobFuncANDROID = extern "system" fn (
-    *const std::os::raw::c_void,
+) -> ()>;
+pub type EGLGetBlobFuncANDROID = Option<unsafe extern "system" fn (
+    *const core::ffi::c_void,
     EGLsizeiANDROID,
-    *mut std::os::raw::c_void,
+    *mut core::ffi::c_void,
     EGLsizeiANDROID
-) -> EGLsizeiANDROID;
-pub type EGLDEBUGPROCKHR = extern "system" fn (
+) -> EGLsizeiANDROID>;
+pub type EGLDEBUGPROCKHR = Option<unsafe extern "system" fn (
     error: EGLenum,
-    command: *mut std::os::raw::c_char,
+    command: *mut core::ffi::c_char,
     messageType: EGLint,
     threadLabel: EGLLabelKHR,
     objectLabel: EGLLabelKHR,
-    message: *mut std::os::raw::c_char
-) -> ();
+    message: *mut core::ffi::c_char
+) -> ()>;
 
 
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct EGLClientPixmapHI {
-    pData: *const std::os::raw::c_void,
-    iWidth: EGLint,
-    iHeight: EGLint,
-    iStride: EGLint,
+    pub pData: *const core::ffi::c_void,
+    pub iWidth: EGLint,
+    pub iHeight: EGLint,
+    pub iStride: EGLint,
 }
 
-pub type wl_display = std::os::raw::c_void;
-pub type wl_surface = std::os::raw::c_void;
-pub type wl_buffer = std::os::raw::c_void;
-pub type wl_resource = std::os::raw::c_void;
+pub type wl_display = core::ffi::c_void;
+pub type wl_surface = core::ffi::c_void;
+pub type wl_buffer = core::ffi::c_void;
+pub type wl_resource = core::ffi::c_void;
 
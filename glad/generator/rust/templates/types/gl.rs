@@ -1,6 +1,6 @@
 #![allow(dead_code, non_snake_case, non_camel_case_types)]
 
-use std::os::raw;
+use core::ffi as raw;
 
 pub type GLvoid = raw::c_void;
 
@@ -59,7 +59,7 @@ pub type GLeglClientBufferEXT = *const raw::c_void;
 pub type GLeglImageOES = *const raw::c_void;
 
 
-pub type GLDEBUGPROC = extern "system" fn (
+pub type GLDEBUGPROC = Option<unsafe extern "system" fn (
     source: GLenum,
     type_: GLenum,
     id: GLuint,
@@ -67,8 +67,8 @@ pub type GLDEBUGPROC = extern "system" fn (
     length: GLsizei,
     message: *const GLchar,
     userParam: *mut raw::c_void,
-);
-pub type GLDEBUGPROCARB = extern "system" fn (
+)>;
+pub type GLDEBUGPROCARB = Option<unsafe extern "system" fn (
     source: GLenum,
     type_: GLenum,
     id: GLuint,
@@ -76,8 +76,8 @@ pub type GLDEBUGPROCARB = extern "system" fn (
     length: GLsizei,
     message: *const GLchar,
     userParam: *mut raw::c_void,
-);
-pub type GLDEBUGPROCKHR = extern "system" fn (
+)>;
+pub type GLDEBUGPROCKHR = Option<unsafe extern "system" fn (
     source: GLenum,
     type_: GLenum,
     id: GLuint,
@@ -85,13 +85,127 @@ pub type GLDEBUGPROCKHR = extern "system" fn (
     length: GLsizei,
     message: *const GLchar,
     userParam: *mut GLvoid,
-);
-pub type GLDEBUGPROCAMD = extern "system" fn (
+)>;
+pub type GLDEBUGPROCAMD = Option<unsafe extern "system" fn (
     id: GLuint,
     category: GLenum,
     severity: GLenum,
     length: GLsizei,
     message: *const GLchar,
     userParam: *mut GLvoid,
-);
-pub type GLVULKANPROCNV = extern "system" fn ();
+)>;
+pub type GLVULKANPROCNV = Option<unsafe extern "system" fn ()>;
+{% if enum_groups %}
+{% import 'template_utils.rs' as template_utils with context %}
+
+macro_rules! enum_group {
+    ($name:ident, $typ:ty) => (
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Eq, PartialEq, Hash)]
+        pub struct $name($typ);
+
+        impl $name {
+            /// Creates the group value from a raw value, the value is not checked.
+            #[inline] pub const fn from_raw(value: $typ) -> $name { $name(value) }
+            #[inline] pub const fn as_raw(self) -> $typ { self.0 }
+        }
+
+        impl From<$name> for $typ {
+            #[inline] fn from(value: $name) -> $typ { value.0 }
+        }
+    );
+}
+
+macro_rules! bitmask_group {
+    ($name:ident, $typ:ty) => (
+        enum_group!($name, $typ);
+
+        impl $name {
+            #[inline] pub const fn empty() -> $name { $name(0) }
+            #[inline] pub const fn is_empty(self) -> bool { self.0 == 0 }
+            #[inline] pub const fn contains(self, other: $name) -> bool { self.0 & other.0 == other.0 }
+            #[inline] pub const fn intersects(self, other: $name) -> bool { self.0 & other.0 != 0 }
+        }
+
+        impl Default for $name {
+            #[inline] fn default() -> $name { $name(0) }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = $name;
+            #[inline] fn bitor(self, rhs: $name) -> $name { $name(self.0 | rhs.0) }
+        }
+
+        impl core::ops::BitAnd for $name {
+            type Output = $name;
+            #[inline] fn bitand(self, rhs: $name) -> $name { $name(self.0 & rhs.0) }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            #[inline] fn bitor_assign(&mut self, rhs: $name) { self.0 |= rhs.0 }
+        }
+
+        impl core::ops::BitAndAssign for $name {
+            #[inline] fn bitand_assign(&mut self, rhs: $name) { self.0 &= rhs.0 }
+        }
+    );
+}
+
+/// Formats a value as `Name(VALUE)`, unknown values are printed in hex.
+fn debug_enum(f: &mut core::fmt::Formatter, name: &str, value: u32, values: &[(&str, u32)]) -> core::fmt::Result {
+    match values.iter().find(|&&(_, v)| v == value) {
+        Some(&(member, _)) => write!(f, "{}({})", name, member),
+        None => write!(f, "{}({:#x})", name, value),
+    }
+}
+
+/// Formats bits as `Name(BIT_A | BIT_B | 0x..)`, unknown bits are printed in hex.
+///
+/// Only single bit values are used, combined values like `ALL_BARRIER_BITS` are skipped.
+fn debug_bitmask(f: &mut core::fmt::Formatter, name: &str, bits: u32, values: &[(&str, u32)]) -> core::fmt::Result {
+    write!(f, "{}(", name)?;
+
+    let mut remaining = bits;
+    for &(member, value) in values.iter().filter(|&&(_, value)| value.is_power_of_two()) {
+        if remaining & value != 0 {
+            if remaining != bits {
+                f.write_str(" | ")?;
+            }
+            f.write_str(member)?;
+            remaining &= !value;
+        }
+    }
+
+    if remaining != 0 || bits == 0 {
+        if remaining != bits {
+            f.write_str(" | ")?;
+        }
+        write!(f, "{:#x}", remaining)?;
+    }
+
+    f.write_str(")")
+}
+{% for group in enum_groups.values()|sort(attribute='name') %}
+
+{{ 'bitmask_group' if group.is_bitmask else 'enum_group' }}!({{ group.name }}, {{ group.type }});
+{% if group.enums %}
+
+#[allow(non_upper_case_globals)]
+impl {{ group.name }} {
+{% for enum in group.enums %}
+    {{ template_utils.gate(enum) }} pub const {{ enum.name|no_prefix }}: {{ group.name }} = {{ group.name }}(super::enumerations::{{ enum.name|no_prefix }} as {{ group.type }});
+{% endfor %}
+}
+{% endif %}
+
+impl core::fmt::Debug for {{ group.name }} {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        {{ 'debug_bitmask' if group.is_bitmask else 'debug_enum' }}(f, "{{ group.name }}", self.0 as u32, &[
+{% for enum in group.enums %}
+            ("{{ enum.name|no_prefix }}", {{ constants[enum.name].value }} as u32),
+{% endfor %}
+        ])
+    }
+}
+{% endfor %}
+{% endif %}
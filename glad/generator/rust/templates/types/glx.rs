@@ -1,23 +1,23 @@
 #![allow(dead_code, non_camel_case_types, non_snake_case)]
 {% include 'types/gl.rs' %}
 
-use std;
+use core;
 
-pub type XID = std::os::raw::c_ulong;
-pub type Bool = std::os::raw::c_int;
+pub type XID = core::ffi::c_ulong;
+pub type Bool = core::ffi::c_int;
 pub enum Display {}
 
 pub type Font = XID;
 pub type Pixmap = XID;
 pub type Colormap = XID;
-pub type Status = XID;
+pub type Status = core::ffi::c_int;
 pub enum Visual {}
-pub type VisualID = std::os::raw::c_ulong;
+pub type VisualID = core::ffi::c_ulong;
 pub type Window = XID;
 pub type GLXFBConfigID = XID;
-pub type GLXFBConfig = *const std::os::raw::c_void;
+pub type GLXFBConfig = *const core::ffi::c_void;
 pub type GLXContextID = XID;
-pub type GLXContext = *const std::os::raw::c_void;
+pub type GLXContext = *const core::ffi::c_void;
 pub type GLXPixmap = XID;
 pub type GLXDrawable = XID;
 pub type GLXWindow = XID;
@@ -25,10 +25,10 @@ pub type GLXPbuffer = XID;
 pub enum __GLXextFuncPtr_fn {}
 pub type __GLXextFuncPtr = *mut __GLXextFuncPtr_fn;
 pub type GLXVideoCaptureDeviceNV = XID;
-pub type GLXVideoDeviceNV = std::os::raw::c_int;
+pub type GLXVideoDeviceNV = core::ffi::c_int;
 pub type GLXVideoSourceSGIX = XID;
 pub type GLXFBConfigIDSGIX = XID;
-pub type GLXFBConfigSGIX = *const std::os::raw::c_void;
+pub type GLXFBConfigSGIX = *const core::ffi::c_void;
 pub type GLXPbufferSGIX = XID;
 
 #[repr(C)]
@@ -36,43 +36,43 @@ pub type GLXPbufferSGIX = XID;
 pub struct XVisualInfo {
     pub visual: *mut Visual,
     pub visualid: VisualID,
-    pub screen: std::os::raw::c_int,
-    pub depth: std::os::raw::c_int,
-    pub class: std::os::raw::c_int,
-    pub red_mask: std::os::raw::c_ulong,
-    pub green_mask: std::os::raw::c_ulong,
-    pub blue_mask: std::os::raw::c_ulong,
-    pub colormap_size: std::os::raw::c_int,
-    pub bits_per_rgb: std::os::raw::c_int,
+    pub screen: core::ffi::c_int,
+    pub depth: core::ffi::c_int,
+    pub class: core::ffi::c_int,
+    pub red_mask: core::ffi::c_ulong,
+    pub green_mask: core::ffi::c_ulong,
+    pub blue_mask: core::ffi::c_ulong,
+    pub colormap_size: core::ffi::c_int,
+    pub bits_per_rgb: core::ffi::c_int,
 }
 
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXPbufferClobberEvent {
-    pub event_type: std::os::raw::c_int,
-    pub draw_type: std::os::raw::c_int,
-    pub serial: std::os::raw::c_ulong,
+    pub event_type: core::ffi::c_int,
+    pub draw_type: core::ffi::c_int,
+    pub serial: core::ffi::c_ulong,
     pub send_event: Bool,
     pub display: *const Display,
     pub drawable: GLXDrawable,
-    pub buffer_mask: std::os::raw::c_uint,
-    pub aux_buffer: std::os::raw::c_uint,
-    pub x: std::os::raw::c_int,
-    pub y: std::os::raw::c_int,
-    pub width: std::os::raw::c_int,
-    pub height: std::os::raw::c_int,
-    pub count: std::os::raw::c_int,
+    pub buffer_mask: core::ffi::c_uint,
+    pub aux_buffer: core::ffi::c_uint,
+    pub x: core::ffi::c_int,
+    pub y: core::ffi::c_int,
+    pub width: core::ffi::c_int,
+    pub height: core::ffi::c_int,
+    pub count: core::ffi::c_int,
 }
 
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXBufferSwapComplete {
-    pub type_: std::os::raw::c_int,
-    pub serial: std::os::raw::c_ulong,
+    pub type_: core::ffi::c_int,
+    pub serial: core::ffi::c_ulong,
     pub send_event: Bool,
     pub display: *const Display,
     pub drawable: GLXDrawable,
-    pub event_type: std::os::raw::c_int,
+    pub event_type: core::ffi::c_int,
     pub ust: i64,
     pub msc: i64,
     pub sbc: i64,
@@ -81,57 +81,64 @@ pub struct GLXBufferSwapComplete {
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXBufferClobberEventSGIX {
-    pub type_: std::os::raw::c_int,
-    pub serial: std::os::raw::c_ulong,
+    pub type_: core::ffi::c_int,
+    pub serial: core::ffi::c_ulong,
     pub send_event: Bool,
     pub display: *const Display,
     pub drawable: GLXDrawable,
-    pub event_type: std::os::raw::c_int,
-    pub draw_type: std::os::raw::c_int,
-    pub mask: std::os::raw::c_uint,
-    pub x: std::os::raw::c_int,
-    pub y: std::os::raw::c_int,
-    pub width: std::os::raw::c_int,
-    pub height: std::os::raw::c_int,
-    pub count: std::os::raw::c_int,
+    pub event_type: core::ffi::c_int,
+    pub draw_type: core::ffi::c_int,
+    pub mask: core::ffi::c_uint,
+    pub x: core::ffi::c_int,
+    pub y: core::ffi::c_int,
+    pub width: core::ffi::c_int,
+    pub height: core::ffi::c_int,
+    pub count: core::ffi::c_int,
 }
 
+{# the hyperpipe types are sized by an enum of GLX_SGIX_hyperpipe and only used by its commands #}
+{% if 'GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX' in feature_set.enums|map(attribute='name')|list %}
+{{ template_utils.gate('GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX') }}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXHyperpipeNetworkSGIX {
-    pub pipeName: [std::os::raw::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
-    pub networkId: std::os::raw::c_int,
+    pub pipeName: [core::ffi::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
+    pub networkId: core::ffi::c_int,
 }
 
+{{ template_utils.gate('GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX') }}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXHyperpipeConfigSGIX {
-    pub pipeName: [std::os::raw::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
-    pub channel: std::os::raw::c_int,
-    pub participationType: std::os::raw::c_uint,
-    pub timeSlice: std::os::raw::c_int,
+    pub pipeName: [core::ffi::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
+    pub channel: core::ffi::c_int,
+    pub participationType: core::ffi::c_uint,
+    pub timeSlice: core::ffi::c_int,
 }
 
+{{ template_utils.gate('GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX') }}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXPipeRect {
-    pub pipeName: [std::os::raw::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
-    pub srcXOrigin: std::os::raw::c_int,
-    pub srcYOrigin: std::os::raw::c_int,
-    pub srcWidth: std::os::raw::c_int,
-    pub srcHeight: std::os::raw::c_int,
-    pub destXOrigin: std::os::raw::c_int,
-    pub destYOrigin: std::os::raw::c_int,
-    pub destWidth: std::os::raw::c_int,
-    pub destHeight: std::os::raw::c_int,
+    pub pipeName: [core::ffi::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
+    pub srcXOrigin: core::ffi::c_int,
+    pub srcYOrigin: core::ffi::c_int,
+    pub srcWidth: core::ffi::c_int,
+    pub srcHeight: core::ffi::c_int,
+    pub destXOrigin: core::ffi::c_int,
+    pub destYOrigin: core::ffi::c_int,
+    pub destWidth: core::ffi::c_int,
+    pub destHeight: core::ffi::c_int,
 }
 
+{{ template_utils.gate('GLX_HYPERPIPE_PIPE_NAME_LENGTH_SGIX') }}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct GLXPipeRectLimits {
-    pub pipeName: [std::os::raw::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
-    pub XOrigin: std::os::raw::c_int,
-    pub YOrigin: std::os::raw::c_int,
-    pub maxHeight: std::os::raw::c_int,
-    pub maxWidth: std::os::raw::c_int,
-}
\ No newline at end of file
+    pub pipeName: [core::ffi::c_char; super::enumerations::HYPERPIPE_PIPE_NAME_LENGTH_SGIX as usize],
+    pub XOrigin: core::ffi::c_int,
+    pub YOrigin: core::ffi::c_int,
+    pub maxHeight: core::ffi::c_int,
+    pub maxWidth: core::ffi::c_int,
+}
+{% endif %}
\ No newline at end of file
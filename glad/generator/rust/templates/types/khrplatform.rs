@@ -1,6 +1,6 @@
 #![allow(non_camel_case_types)]
 
-use std;
+use core;
 
 // see khrplatform.h for these types
 pub type khronos_int8_t  = i8;
@@ -15,7 +15,7 @@ pub type khronos_intptr_t = isize;
 pub type khronos_uintptr_t = usize;
 pub type khronos_ssize_t  = isize;
 pub type khronos_usize_t  = usize;
-pub type khronos_float_t = std::os::raw::c_float;
+pub type khronos_float_t = core::ffi::c_float;
 pub type khronos_time_ns_t = u64;
 pub type khronos_stime_nanoseconds_t = i64;
 pub type khronos_utime_nanoseconds_t = u64;
@@ -2,41 +2,41 @@
 
 {% import 'template_utils.rs' as template_utils with context %}
 
-use std;
-use std::os::raw::*;
+use core;
+use core::ffi::*;
 
 // types required for: xcb
-pub type xcb_connection_t = std::os::raw::c_void;
+pub type xcb_connection_t = core::ffi::c_void;
 pub type xcb_window_t = u32;
 pub type xcb_visualid_t = u32;
 // types required for: xlib(_xrandr)
-pub type Display = std::os::raw::c_void;
-pub type RROutput = std::os::raw::c_ulong;
-pub type Window = std::os::raw::c_ulong;
-pub type VisualID = std::os::raw::c_ulong;
+pub type Display = core::ffi::c_void;
+pub type RROutput = core::ffi::c_ulong;
+pub type Window = core::ffi::c_ulong;
+pub type VisualID = core::ffi::c_ulong;
 // types required for: win32
-pub type BOOL = std::os::raw::c_int;
-pub type DWORD = std::os::raw::c_ulong;
-pub type LPVOID = *mut std::os::raw::c_void;
-pub type HANDLE = *mut std::os::raw::c_void;
-pub type HMONITOR = *mut std::os::raw::c_void;
+pub type BOOL = core::ffi::c_int;
+pub type DWORD = core::ffi::c_ulong;
+pub type LPVOID = *mut core::ffi::c_void;
+pub type HANDLE = *mut core::ffi::c_void;
+pub type HMONITOR = *mut core::ffi::c_void;
 pub type WCHAR = u16;
 pub type LPCWSTR = *const WCHAR;
-pub type HINSTANCE = *mut std::os::raw::c_void;
-pub type HWND = *mut std::os::raw::c_void;
+pub type HINSTANCE = *mut core::ffi::c_void;
+pub type HWND = *mut core::ffi::c_void;
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct SECURITY_ATTRIBUTES {
-    nLength: DWORD,
-    lpSecurityDescriptor: LPVOID,
-    bInheritHandle: BOOL,
+    pub nLength: DWORD,
+    pub lpSecurityDescriptor: LPVOID,
+    pub bInheritHandle: BOOL,
 }
 // types required for: wayland
-pub type wl_display = std::os::raw::c_void;
-pub type wl_surface = std::os::raw::c_void;
+pub type wl_display = core::ffi::c_void;
+pub type wl_surface = core::ffi::c_void;
 // types required for: mir
-pub type MirConnection = std::os::raw::c_void;
-pub type MirSurface = std::os::raw::c_void;
+pub type MirConnection = core::ffi::c_void;
+pub type MirSurface = core::ffi::c_void;
 
 
 #[macro_export]
@@ -55,7 +55,7 @@ macro_rules! VK_VERSION_PATCH { ($version:expr) => ($version & 0xfff); }
 macro_rules! VK_DEFINE_NON_DISPATCHABLE_HANDLE {
     ($name:ident) => (
         #[repr(C)]
-        #[derive(Copy, Clone)]
+        #[derive(Copy, Clone, Debug)]
         pub struct $name(u64);
     );
 }
@@ -64,11 +64,150 @@ macro_rules! VK_DEFINE_NON_DISPATCHABLE_HANDLE {
 macro_rules! VK_DEFINE_HANDLE {
     ($name:ident) => (
         #[repr(C)]
-        #[derive(Copy, Clone)]
-        pub struct $name(*const std::os::raw::c_void);
+        #[derive(Copy, Clone, Debug)]
+        pub struct $name(*const core::ffi::c_void);
+
+        unsafe impl Send for $name {}
+        unsafe impl Sync for $name {}
+    );
+}
+
+/// Common header of all structs which can be part of a `pNext` chain.
+#[repr(C)]
+struct BaseStructure {
+    sType: VkStructureType,
+    pNext: *mut BaseStructure,
+}
+
+/// Inserts `next` and its chain between `head` and the rest of the chain of `head`.
+unsafe fn push_next(head: *mut BaseStructure, next: *mut BaseStructure) {
+    let mut last = next;
+    while !(*last).pNext.is_null() {
+        last = (*last).pNext;
+    }
+
+    (*last).pNext = (*head).pNext;
+    (*head).pNext = next;
+}
+
+macro_rules! bitflags {
+    ($name:ident, $typ:ty) => (
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+        pub struct $name(pub $typ);
+
+        impl $name {
+            #[inline] pub const fn empty() -> $name { $name(0) }
+            #[inline] pub const fn from_raw(bits: $typ) -> $name { $name(bits) }
+            #[inline] pub const fn as_raw(self) -> $typ { self.0 }
+            #[inline] pub const fn is_empty(self) -> bool { self.0 == 0 }
+            #[inline] pub const fn contains(self, other: $name) -> bool { self.0 & other.0 == other.0 }
+            #[inline] pub const fn intersects(self, other: $name) -> bool { self.0 & other.0 != 0 }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = $name;
+            #[inline] fn bitor(self, rhs: $name) -> $name { $name(self.0 | rhs.0) }
+        }
+
+        impl core::ops::BitAnd for $name {
+            type Output = $name;
+            #[inline] fn bitand(self, rhs: $name) -> $name { $name(self.0 & rhs.0) }
+        }
+
+        impl core::ops::BitXor for $name {
+            type Output = $name;
+            #[inline] fn bitxor(self, rhs: $name) -> $name { $name(self.0 ^ rhs.0) }
+        }
+
+        impl core::ops::Not for $name {
+            type Output = $name;
+            #[inline] fn not(self) -> $name { $name(!self.0) }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            #[inline] fn bitor_assign(&mut self, rhs: $name) { self.0 |= rhs.0 }
+        }
+
+        impl core::ops::BitAndAssign for $name {
+            #[inline] fn bitand_assign(&mut self, rhs: $name) { self.0 &= rhs.0 }
+        }
+
+        impl core::ops::BitXorAssign for $name {
+            #[inline] fn bitxor_assign(&mut self, rhs: $name) { self.0 ^= rhs.0 }
+        }
+    );
+}
+
+/// Formats bits as `Name(FLAG_A | FLAG_B | 0x..)`, unknown bits are printed in hex.
+fn debug_flags(f: &mut core::fmt::Formatter, name: &str, bits: u64, flags: &[(&str, u64)]) -> core::fmt::Result {
+    write!(f, "{}(", name)?;
+
+    let mut remaining = bits;
+    for &(flag, value) in flags {
+        if remaining & value != 0 {
+            if remaining != bits {
+                f.write_str(" | ")?;
+            }
+            f.write_str(flag)?;
+            remaining &= !value;
+        }
+    }
+
+    if remaining != 0 || bits == 0 {
+        if remaining != bits {
+            f.write_str(" | ")?;
+        }
+        write!(f, "{:#x}", remaining)?;
+    }
+
+    f.write_str(")")
+}
+
+{% if options.newtype_enums %}
+macro_rules! enumeration {
+    ($name:ident, $typ:ty) => (
+        #[repr(transparent)]
+        #[derive(Copy, Clone, Eq, PartialEq, Hash)]
+        pub struct $name(pub $typ);
+
+        impl $name {
+            #[inline] pub const fn from_raw(value: $typ) -> $name { $name(value) }
+            #[inline] pub const fn as_raw(self) -> $typ { self.0 }
+        }
     );
 }
 
+/// Formats a value as the name of its member, unknown values are printed as `Name(value)`.
+fn debug_enum(f: &mut core::fmt::Formatter, name: &str, value: i64, members: &[(&str, i64)]) -> core::fmt::Result {
+    match members.iter().find(|&&(_, v)| v == value) {
+        Some(&(member, _)) => f.write_str(member),
+        None => write!(f, "{}({})", name, value),
+    }
+}
+
+{% endif %}
+{% macro bitflags(name, type, members) %}
+bitflags!({{ name }}, {{ type }});
+{% if members %}
+
+impl {{ name }} {
+{% for member in members %}
+    pub const {{ member.name }}: {{ name }} = {{ name }}({{ member.value if not member.alias else name + '::' + member.alias + '.0' }});
+{% endfor %}
+}
+{% endif %}
+
+impl core::fmt::Debug for {{ name }} {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        debug_flags(f, "{{ name }}", self.0 as u64, &[
+{% for member in members if member.bitpos is not none and not member.alias %}
+            ("{{ member.name }}", {{ member.value }}),
+{% endfor %}
+        ])
+    }
+}
+{% endmacro %}
 {% for type in feature_set.types %}
 {% if type.alias %}
 pub type {{ type.name }} = {{ type.alias }};
@@ -76,6 +215,35 @@ pub type {{ type.name }} = {{ type.alias }};
 pub type {{ type.name }} = {{ type.type|type }};
 {% elif type.category == 'handle' %}
 {{ type.type }}!({{ type.name }});
+{% elif type.category == 'enum' and type.name in bitmasks %}
+pub type {{ type.name }} = {{ bitmasks[type.name] }};
+{% elif type.category == 'enum' and type.is_bitmask %}
+{{ bitflags(type.name, 'VkFlags64' if type.bitwidth == '64' else 'VkFlags', type.enums_for(feature_set)) }}
+{% elif type.category == 'enum' and options.newtype_enums %}
+{% set members = type.enums_for(feature_set) %}
+{% if members %}
+{{ template_utils.protect(type) }}
+enumeration!({{ type.name }}, i{{ type.bitwidth or '32' }});
+
+{{ template_utils.protect(type) }}
+#[allow(non_upper_case_globals)]
+impl {{ type.name }} {
+{% for member in members %}
+    pub const {{ member.name }}: {{ type.name }} = {{ type.name }}({{ member.value if not member.alias else type.name + '::' + member.alias + '.0' }});
+{% endfor %}
+}
+
+{{ template_utils.protect(type) }}
+impl core::fmt::Debug for {{ type.name }} {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        debug_enum(f, "{{ type.name }}", self.0 as i64, &[
+{% for member in members if not member.alias %}
+            ("{{ member.name }}", {{ member.value }}),
+{% endfor %}
+        ])
+    }
+}
+{% endif %}
 {% elif type.category == 'enum' %}
 {% set members = type.enums_for(feature_set) %}
 {% if members %}
@@ -89,6 +257,16 @@ pub enum {{ type.name }} {
 {% endif %}
 {% endfor %}
 }
+{% set alias_members = members|selectattr('alias')|list %}
+{% if alias_members %}
+{{ template_utils.protect(type) }}
+impl {{ type.name }} {
+{% for member in alias_members %}
+    pub const {{ member.name }}: {{ type.name }} = {{ type.name }}::{{ member.alias }};
+{% endfor %}
+}
+
+{% endif %}
 {% endif %}
 {% elif type.category in ('struct', 'union') %}
 {{ template_utils.protect(type) }}
@@ -96,17 +274,115 @@ pub enum {{ type.name }} {
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub {{ type.category }} {{ type.name }} {
+{% set units = bitfields.get(type.name, {}) %}
 {% for member in type.members %}
-    {{ member.name|identifier }}: {{ member.type|type }},
+{% if member.type.bitfield is none %}
+    pub {{ member.name|identifier }}: {{ member.type|type }},
+{% elif member.name in units %}
+    pub {{ units[member.name].name }}: {{ units[member.name].type }},
+{% endif %}
 {% endfor %}
 }
+{% if units %}
+{{ template_utils.protect(type) }}
+impl {{ type.name }} {
+{% for unit in units.values() %}
+{% for field in unit.fields %}
+    #[inline] pub fn {{ field.name }}(&self) -> {{ field.type|type }} {
+        let bits = (self.{{ unit.name }} >> {{ field.offset }}) & {{ field.mask }};
+{% if field.flags %}
+        {{ field.type|type }}::from_raw(bits as {{ field.flags }})
+{% else %}
+        bits as {{ field.type|type }}
+{% endif %}
+    }
+
+    #[inline] pub fn set_{{ field.name }}(&mut self, {{ field.name }}: {{ field.type|type }}) {
+        let bits = ({{ field.name }}{{ '.as_raw()' if field.flags else '' }} as {{ unit.type }}) & {{ field.mask }};
+        self.{{ unit.name }} = (self.{{ unit.name }} & !({{ field.mask }} << {{ field.offset }})) | (bits << {{ field.offset }});
+    }
+
+{% endfor %}
+{% endfor %}
+}
+{% endif %}
+{% if type.name in default_types %}
+{% set typed_members = type.members|selectattr('values')|list %}
+{{ template_utils.protect(type) }}
+impl Default for {{ type.name }} {
+    fn default() -> {{ type.name }} {
+{% if typed_members %}
+        {{ type.name }} {
+{% for member in typed_members %}
+            {{ member.name|identifier }}: {{ member.type.type }}::{{ member.values[0] }},
+{% endfor %}
+            ..unsafe { core::mem::zeroed() }
+        }
+{% else %}
+        unsafe { core::mem::zeroed() }
+{% endif %}
+    }
+}
+{% if type.category == 'struct' and typed_members %}
+{{ template_utils.protect(type) }}
+impl {{ type.name }} {
+    pub fn builder<'a>() -> {{ type.name }}Builder<'a> {
+        {{ type.name }}Builder { inner: {{ type.name }}::default(), marker: core::marker::PhantomData }
+    }
+}
+{{ template_utils.protect(type) }}
+#[repr(transparent)]
+pub struct {{ type.name }}Builder<'a> {
+    inner: {{ type.name }},
+    marker: core::marker::PhantomData<&'a ()>,
+}
+{{ template_utils.protect(type) }}
+impl<'a> {{ type.name }}Builder<'a> {
+{% for setter in type|setters %}
+    #[inline] pub fn {{ setter.name }}(mut self, {{ setter.name }}: {{ setter.type }}) -> {{ type.name }}Builder<'a> {
+{% for field, value in setter.assignments %}
+        self.inner.{{ field }} = {{ value }};
+{% endfor %}
+        self
+    }
+
+{% endfor %}
+{% if type.name in extended_by %}
+    /// Prepends `next`, including its own `pNext` chain, to the `pNext` chain.
+    pub fn push_next<T: Extends{{ type.name }}>(mut self, next: &'a mut T) -> {{ type.name }}Builder<'a> {
+        unsafe { push_next(&mut self.inner as *mut _ as *mut BaseStructure, next as *mut T as *mut BaseStructure) };
+        self
+    }
+
+{% endif %}
+}
+{{ template_utils.protect(type) }}
+impl<'a> core::ops::Deref for {{ type.name }}Builder<'a> {
+    type Target = {{ type.name }};
+
+    fn deref(&self) -> &{{ type.name }} {
+        &self.inner
+    }
+}
+{% endif %}
+{% endif %}
+{% if type.name in extended_by %}
+{{ template_utils.protect(type) }}
+/// Structs which can be chained into `{{ type.name }}` through `pNext`.
+pub unsafe trait Extends{{ type.name }} {}
+{% for extension in extended_by[type.name] %}
+{{ template_utils.protect(type) }}{{ template_utils.protect(extension) }}
+unsafe impl Extends{{ type.name }} for {{ extension.name }} {}
+{% endfor %}
+{% endif %}
 {% elif type.category == 'bitmask' %}
-pub type {{ type.name }} = {{ type.type }};
+{% set bits = bitmask_bits[type.name] %}
+{{ bitflags(type.name, type.type, bits.enums_for(feature_set) if bits else []) }}
 {% elif type.category == 'funcpointer' %}
-pub type {{ type.name }} = extern "system" fn(
+pub type {{ type.name }} = Option<unsafe extern "system" fn(
 {% for parameter in type.parameters %}
     {{ parameter.name }}: {{ parameter.type|type }},
 {% endfor %}
-) -> {{ type.ret|type }};
+) -> {{ type.ret|type }}>;
 {% endif %}
 {% endfor %}
@@ -1,47 +1,47 @@
 #![allow(dead_code, non_camel_case_types, non_snake_case)]
 {% include 'types/gl.rs' %}
 
-use std;
+use core;
 
-pub type BOOL = std::os::raw::c_int;
-pub type BYTE = std::os::raw::c_uchar;
-pub type CHAR = std::os::raw::c_char;
+pub type BOOL = core::ffi::c_int;
+pub type BYTE = core::ffi::c_uchar;
+pub type CHAR = core::ffi::c_char;
 pub type COLORREF = DWORD;
-pub type DWORD = std::os::raw::c_ulong;
-pub type FLOAT = std::os::raw::c_float;
+pub type DWORD = core::ffi::c_ulong;
+pub type FLOAT = core::ffi::c_float;
 pub type HANDLE = PVOID;
 pub type HDC = HANDLE;
 pub type HENHMETAFILE = HANDLE;
-pub type HGLRC = *const std::os::raw::c_void;
-pub type HGPUNV = *const std::os::raw::c_void;
-pub type HPBUFFERARB = *const std::os::raw::c_void;
-pub type HPBUFFEREXT = *const std::os::raw::c_void;
-pub type HPGPUNV = *const std::os::raw::c_void;
-pub type HPVIDEODEV = *const std::os::raw::c_void;
-pub type HVIDEOINPUTDEVICENV = *const std::os::raw::c_void;
-pub type HVIDEOOUTPUTDEVICENV = *const std::os::raw::c_void;
-pub type INT = std::os::raw::c_int;
+pub type HGLRC = *const core::ffi::c_void;
+pub type HGPUNV = *const core::ffi::c_void;
+pub type HPBUFFERARB = *const core::ffi::c_void;
+pub type HPBUFFEREXT = *const core::ffi::c_void;
+pub type HPGPUNV = *const core::ffi::c_void;
+pub type HPVIDEODEV = *const core::ffi::c_void;
+pub type HVIDEOINPUTDEVICENV = *const core::ffi::c_void;
+pub type HVIDEOOUTPUTDEVICENV = *const core::ffi::c_void;
+pub type INT = core::ffi::c_int;
 pub type INT32 = i32;
 pub type INT64 = i64;
-pub type LONG = std::os::raw::c_long;
-pub type LPCSTR = *const std::os::raw::c_char;
-pub type LPVOID = *const std::os::raw::c_void;
-pub type PVOID = *const std::os::raw::c_void;
-pub type UINT = std::os::raw::c_uint;
-pub type USHORT = std::os::raw::c_ushort;
+pub type LONG = core::ffi::c_long;
+pub type LPCSTR = *const core::ffi::c_char;
+pub type LPVOID = *const core::ffi::c_void;
+pub type PVOID = *const core::ffi::c_void;
+pub type UINT = core::ffi::c_uint;
+pub type USHORT = core::ffi::c_ushort;
 pub type VOID = ();
-pub type WORD = std::os::raw::c_ushort;
+pub type WORD = core::ffi::c_ushort;
 
 pub enum __PROC_fn {}
 pub type PROC = *mut __PROC_fn;
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct RECT {
-    left: LONG,
-    top: LONG,
-    right: LONG,
-    bottom: LONG,
+    pub left: LONG,
+    pub top: LONG,
+    pub right: LONG,
+    pub bottom: LONG,
 }
 
 #[repr(C)]
@@ -123,14 +123,16 @@ pub struct PIXELFORMATDESCRIPTOR {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct _GPU_DEVICE {
-    cb: DWORD,
-    DeviceName: [CHAR; 32],
-    DeviceString: [CHAR; 128],
-    Flags: DWORD,
-    rcVirtualScreen: RECT,
+    pub cb: DWORD,
+    pub DeviceName: [CHAR; 32],
+    pub DeviceString: [CHAR; 128],
+    pub Flags: DWORD,
+    pub rcVirtualScreen: RECT,
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct GPU_DEVICE(_GPU_DEVICE);
+#[derive(Copy, Clone, Debug)]
 pub struct PGPU_DEVICE(*const _GPU_DEVICE);
@@ -35,7 +35,29 @@ import glad.util
 logger = logging.getLogger(__name__)
 
 
-_ARRAY_RE = re.compile(r'\[(\d+)\]')
+_ARRAY_RE = re.compile(r'\[\s*(\w+)\s*\]')
+_BITFIELD_RE = re.compile(r':\s*(\d+)\s*$')
+
+
+def _parse_declarator(raw):
+    """
+    Parses the array dimensions and the bitfield width of a declaration.
+
+    E.g.:
+        float matrix[3][4] -> ([3, 4], None)
+        char name[VK_MAX_EXTENSION_NAME_SIZE] -> (['VK_MAX_EXTENSION_NAME_SIZE'], None)
+        uint32_t mask:8 -> ([], 8)
+
+    :param raw: the raw declaration
+    :return: tuple of the array dimensions, numbers or names of constants, and the bitfield width or None
+    """
+    if raw is None:
+        return [], None
+
+    dimensions = [int(d) if d.isdigit() else d for d in _ARRAY_RE.findall(raw)]
+    bitfield_match = _BITFIELD_RE.search(raw)
+    bitfield = None if bitfield_match is None else int(bitfield_match.group(1))
+    return dimensions, bitfield
 
 
 class FeatureSetInfo(object):
@@ -344,6 +366,7 @@ class Specification(object):
                 assert t.api is None
                 t.api = api
                 t.bitwidth = enums_element.get('bitwidth')
+                t.is_bitmask = enums_element.get('type') == 'bitmask'
 
                 kwargs = dict(namespace=enums_element.get('namespace'),
                               parent_group=enums_element.get('group'),
@@ -410,7 +433,8 @@ class Specification(object):
                 while aliased_command.proto is None:
                     aliased_command = next(c for c in commands[aliased_command.alias] if c.api == command.api)
 
-                command.proto = Proto(command.name, copy.deepcopy(aliased_command.proto.ret))
+                command.proto = Proto(command.name, copy.deepcopy(aliased_command.proto.ret),
+                                      aliased_command.proto.object_class)
                 command.params = copy.deepcopy(aliased_command.params)
 
         return commands
@@ -953,15 +977,26 @@ class UnionType(MemberType):
 
 
 class StructType(MemberType):
-    pass
+    def __init__(self, name, structextends=None, **kwargs):
+        MemberType.__init__(self, name, **kwargs)
+
+        # names of the structs this struct can be chained into through pNext
+        self.structextends = structextends or []
+
+    @classmethod
+    def factory(cls, element, name, data):
+        structextends = [s for s in element.get('structextends', '').split(',') if s]
+        data = dict(data, structextends=structextends)
+        return super(StructType, cls).factory(element, name, data)
 
 
 class EnumType(Type):
-    def __init__(self, name, enums=None, bitwidth=None, **kwargs):
+    def __init__(self, name, enums=None, bitwidth=None, is_bitmask=False, **kwargs):
         Type.__init__(self, name, **kwargs)
 
         self.enums = enums or []
         self.bitwidth = bitwidth
+        self.is_bitmask = is_bitmask
 
     @property
     def expanded_name(self):
@@ -1009,7 +1044,16 @@ class BasetypeType(TypedType):
 
 
 class BitmaskType(TypedType):
-    pass
+    def __init__(self, name, bitvalues=None, **kwargs):
+        TypedType.__init__(self, name, **kwargs)
+
+        # name of the enum type containing the bits of this bitmask
+        self.bitvalues = bitvalues
+
+    @classmethod
+    def factory(cls, element, name, data):
+        data = dict(data, bitvalues=element.get('bitvalues') or element.get('requires'))
+        return super(BitmaskType, cls).factory(element, name, data)
 
 
 Type.register('funcpointer', FuncPointerType.factory)
@@ -1022,19 +1066,27 @@ Type.register('bitmask', BitmaskType.factory)
 
 
 class Member(IdentifiedByName):
-    def __init__(self, name, type_, api=None, enum=None):
+    def __init__(self, name, type_, api=None, enum=None, values=None, len=None, altlen=None):
         self.name = name
         self.type = type_
         self.api = api
         self.enum = enum
+        # legal values of the member, e.g. the sType of a struct
+        self.values = values or []
+        # length of a pointer member, e.g. the name of the member holding the count
+        self.len = len
+        # C expression of the length if `len` is written in latexmath
+        self.altlen = altlen
 
     @classmethod
     def from_element(cls, element):
         type_ = ParsedType.from_element(element)
         enum = element.find('enum')
         api = element.get('api')
+        values = [v for v in element.get('values', '').split(',') if v]
 
-        return Member(type_.name, type_, api=api, enum=enum.text if enum is not None else None)
+        return Member(type_.name, type_, api=api, enum=enum.text if enum is not None else None,
+                      values=values, len=element.get('len'), altlen=element.get('altlen'))
 
     def __str__(self):
         return 'Member(name={self.name}, type={self.type})'.format(self=self)
@@ -1204,13 +1256,15 @@ class Command(IdentifiedByName):
 
 
 class Proto(object):
-    def __init__(self, name, ret):
+    def __init__(self, name, ret, object_class=None):
         self.name = name
         self.ret = ret
+        # class of the object the command returns, e.g. `program`
+        self.object_class = object_class
 
     @classmethod
     def from_element(cls, element):
-        return Proto(element.find('name').text, ParsedType.from_element(element))
+        return Proto(element.find('name').text, ParsedType.from_element(element), element.get('class'))
 
     def is_equivalent(self, other):
         return self.ret == other.ret
@@ -1225,6 +1279,12 @@ class Param(object):
         self.type = ParsedType.from_element(element)
         self.name = element.find('name').text.strip('*')
         self.api = element.get('api')
+        # length of an array parameter, e.g. `count`, `null-terminated` or `COMPSIZE(pname)`
+        self.len = element.get('len')
+        # one entry per indirection, whether NULL is a valid value
+        self.optional = [o.strip() == 'true' for o in element.get('optional', '').split(',') if o]
+        # class of the object the parameter names, e.g. `buffer`
+        self.object_class = element.get('class')
 
     def is_equivalent(self, other):
         return self.type == other.type
@@ -1236,13 +1296,16 @@ class Param(object):
 class ParsedType(object):
     def __init__(self, name, type_, original_type,
                  is_pointer=0, is_array=0, is_struct=False, is_const=False,
-                 is_unsigned=False, comment='', raw=None, element=None):
+                 is_unsigned=False, comment='', raw=None, element=None,
+                 array_dimensions=None, bitfield=None):
         self.name = name
         self.original_type = original_type
         self.type = type_
 
         self.is_pointer = is_pointer
         self.is_array = is_array
+        self.array_dimensions = array_dimensions or []
+        self.bitfield = bitfield
         self.is_struct = is_struct
         self.is_const = is_const
         self.is_unsigned = is_unsigned
@@ -1265,16 +1328,17 @@ class ParsedType(object):
 
         # 0 if no pointer, 1 if *, 2 if **
         is_pointer = 0 if raw is None else raw.count('*')
-        array_match = _ARRAY_RE.search(raw)
-        is_array = 0 if array_match is None else int(array_match.group(1))
+        # 0 if no array, 1 if [N], 2 if [N][M]
+        array_dimensions, bitfield = _parse_declarator(raw)
+        is_array = len(array_dimensions)
         is_const = False if raw is None else 'const' in raw
         is_unsigned = False if raw is None else 'unsigned' in raw
         is_struct = 'struct' in raw
 
         return cls(None, type_, raw,
-                   is_pointer=is_pointer, is_struct=is_struct,
+                   is_pointer=is_pointer, is_array=is_array, is_struct=is_struct,
                    is_const=is_const, is_unsigned=is_unsigned,
-                   raw=raw)
+                   raw=raw, array_dimensions=array_dimensions, bitfield=bitfield)
 
     @classmethod
     def from_element(cls, element):
@@ -1299,8 +1363,9 @@ class ParsedType(object):
 
         # 0 if no pointer, 1 if *, 2 if **
         is_pointer = 0 if raw is None else raw.count('*')
-        array_match = _ARRAY_RE.search(raw)
-        is_array = 0 if array_match is None else int(array_match.group(1))
+        # 0 if no array, 1 if [N], 2 if [N][M]
+        array_dimensions, bitfield = _parse_declarator(raw)
+        is_array = len(array_dimensions)
         is_const = False if raw is None else 'const' in raw
         is_unsigned = False if raw is None else 'unsigned' in raw
         is_struct = 'struct' in raw
@@ -1308,7 +1373,8 @@ class ParsedType(object):
         return cls(name, type_, original_type,
                    is_pointer=is_pointer, is_array=is_array,
                    is_struct=is_struct, is_const=is_const, is_unsigned=is_unsigned,
-                   comment=comment, raw=raw, element=element)
+                   comment=comment, raw=raw, element=element,
+                   array_dimensions=array_dimensions, bitfield=bitfield)
 
 
 # TODO unify API
@@ -1346,13 +1412,15 @@ class Remove(object):
 
 class Extension(IdentifiedByName):
     def __init__(self, name, supported=None, requires=None,
-                 type_=None, protect=None, platform=None):
+                 type_=None, protect=None, platform=None, depends=None):
         self.name = name
         self.supported = supported
         self.requires = requires or []
         self.type = type_
         self.protect = protect or []
         self.platform = platform
+        # dependency expression, `+` for all of, `,` for one of, e.g. `VK_KHR_surface+(VK_VERSION_1_1,VK_KHR_maintenance1)`
+        self.depends = depends
 
     @classmethod
     def from_element(cls, element):
@@ -1365,9 +1433,10 @@ class Extension(IdentifiedByName):
         type_ = element.get('type')
         protect = [p.strip() for p in element.get('protect', '').split(',') if p.strip()]
         platform = element.get('platform')
+        depends = element.get('depends')
 
         return cls(name, supported=supported, requires=requires,
-                   type_=type_, protect=protect, platform=platform)
+                   type_=type_, protect=protect, platform=platform, depends=depends)
 
     def supports(self, api):
         return api in self.supported
@@ -0,0 +1,28 @@
+#![deny(warnings)]
+/**
+ * Full core GL with debug callbacks, should compile
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --debug
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use std::fmt::Debug;
+
+fn pre_call(name: &'static str, args: &[&dyn Debug]) {
+    println!("{}({:?})", name, args);
+}
+
+fn post_call(ret: &dyn Debug, name: &'static str, _args: &[&dyn Debug]) {
+    println!("{} -> {:?}", name, ret);
+}
+
+#[allow(path_statements)]
+fn main() {
+    gl::set_pre_callback(pre_call);
+    gl::set_post_callback(post_call);
+
+    gl::Clear;
+    gl::MultiDrawElementsEXT;
+}
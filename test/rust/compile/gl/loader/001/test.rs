@@ -0,0 +1,18 @@
+#![deny(warnings)]
+/**
+ * The builtin loader should compile and load without a windowing library
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --loader
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+
+fn main() {
+    match gl::load_from_system() {
+        Ok(()) => println!("loaded system GL library"),
+        Err(gl::LoaderError::LibraryNotFound { names }) => println!("no system GL library: {:?}", names),
+        Err(err) => panic!("{}", err),
+    }
+}
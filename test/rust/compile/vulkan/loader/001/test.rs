@@ -0,0 +1,18 @@
+#![deny(warnings)]
+/**
+ * The builtin loader should compile and report a missing Vulkan loader as error
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_swapchain" rust --loader
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk;
+
+fn main() {
+    match vk::load_from_system() {
+        Ok(()) => println!("loaded system Vulkan loader"),
+        Err(err @ vk::LoaderError::LibraryNotFound { .. }) => println!("{}", err),
+        Err(err) => panic!("{}", err),
+    }
+}
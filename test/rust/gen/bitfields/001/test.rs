@@ -0,0 +1,33 @@
+#![deny(warnings)]
+/**
+ * Bitfields are packed into storage units with accessors
+ * and arrays can have several dimensions or the length of a constant
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_acceleration_structure" rust --layout-tests
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run && cd glad-vulkan && cargo test --test layout
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk;
+
+fn main() {
+    let mut instance: vk::VkAccelerationStructureInstanceKHR = unsafe { std::mem::zeroed() };
+    instance.set_instanceCustomIndex(0x123456);
+    instance.set_mask(0xff);
+    instance.set_instanceShaderBindingTableRecordOffset(0x1ffffff);
+    instance.set_flags(vk::VkGeometryInstanceFlagsKHR::VK_GEOMETRY_INSTANCE_FORCE_OPAQUE_BIT_KHR);
+
+    assert_eq!(instance.instanceCustomIndex(), 0x123456);
+    assert_eq!(instance.mask(), 0xff);
+    assert_eq!(instance.instanceCustomIndex_and_mask, 0xff123456);
+    assert_eq!(instance.instanceShaderBindingTableRecordOffset(), 0xffffff);
+    assert_eq!(instance.flags(), vk::VkGeometryInstanceFlagsKHR::VK_GEOMETRY_INSTANCE_FORCE_OPAQUE_BIT_KHR);
+    assert_eq!(std::mem::size_of::<vk::VkAccelerationStructureInstanceKHR>(), 64);
+
+    let transform = vk::VkTransformMatrixKHR { matrix: [[0.0; 4]; 3] };
+    assert_eq!(transform.matrix.len(), 3);
+    assert_eq!(transform.matrix[0].len(), 4);
+
+    let properties: vk::VkExtensionProperties = unsafe { std::mem::zeroed() };
+    assert_eq!(properties.extensionName.len(), vk::MAX_EXTENSION_NAME_SIZE as usize);
+}
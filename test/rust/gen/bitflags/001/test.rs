@@ -0,0 +1,31 @@
+#![deny(warnings)]
+/**
+ * Bitmasks should be typed flags which can be combined and printed,
+ * aliased enum members should be available as constants
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_surface,VK_KHR_swapchain,VK_KHR_maintenance1" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::*;
+
+fn main() {
+    let usage = VkBufferUsageFlags::VK_BUFFER_USAGE_VERTEX_BUFFER_BIT | VkBufferUsageFlagBits::VK_BUFFER_USAGE_TRANSFER_DST_BIT;
+
+    assert!(usage.contains(VkBufferUsageFlags::VK_BUFFER_USAGE_VERTEX_BUFFER_BIT));
+    assert!(!usage.contains(VkBufferUsageFlags::VK_BUFFER_USAGE_INDEX_BUFFER_BIT));
+    assert!((usage & VkBufferUsageFlags::VK_BUFFER_USAGE_TRANSFER_DST_BIT).as_raw() == 0x2);
+    assert!(VkBufferUsageFlags::empty().is_empty());
+    assert_eq!(std::mem::size_of::<VkBufferUsageFlags>(), std::mem::size_of::<VkFlags>());
+
+    assert_eq!(
+        format!("{:?}", usage),
+        "VkBufferUsageFlags(VK_BUFFER_USAGE_TRANSFER_DST_BIT | VK_BUFFER_USAGE_VERTEX_BUFFER_BIT)"
+    );
+    assert_eq!(format!("{:?}", VkBufferUsageFlags::from_raw(0x1 | 0x40000000)), "VkBufferUsageFlags(VK_BUFFER_USAGE_TRANSFER_SRC_BIT | 0x40000000)");
+    assert_eq!(format!("{:?}", VkBufferUsageFlags::empty()), "VkBufferUsageFlags(0x0)");
+
+    assert_eq!(VkColorSpaceKHR::VK_COLORSPACE_SRGB_NONLINEAR_KHR, VkColorSpaceKHR::VK_COLOR_SPACE_SRGB_NONLINEAR_KHR);
+    assert_eq!(VkResult::VK_ERROR_OUT_OF_POOL_MEMORY_KHR, VkResult::VK_ERROR_OUT_OF_POOL_MEMORY);
+}
@@ -0,0 +1,64 @@
+#![deny(warnings)]
+/**
+ * Structs should have public fields, a default with the correct sType
+ * and builders which can extend the pNext chain, borrowing pointed to data
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_surface,VK_KHR_swapchain" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+#[macro_use]
+extern crate glad_vulkan;
+use glad_vulkan::vk::*;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+
+fn main() {
+    let application_info = VkApplicationInfo {
+        apiVersion: VK_MAKE_VERSION!(1, 0, 0),
+        ..Default::default()
+    };
+    assert_eq!(application_info.sType, VkStructureType::VK_STRUCTURE_TYPE_APPLICATION_INFO);
+    assert!(application_info.pNext.is_null());
+
+    let name = CStr::from_bytes_with_nul(b"test\0").unwrap();
+    let application_info = VkApplicationInfo::builder()
+        .pApplicationName(name)
+        .apiVersion(VK_MAKE_VERSION!(1, 0, 0));
+    assert_eq!(application_info.pApplicationName, name.as_ptr());
+
+    let extensions = [b"VK_KHR_surface\0".as_ptr() as *const c_char];
+    let instance_info = VkInstanceCreateInfo::builder()
+        .pApplicationInfo(&application_info)
+        .ppEnabledExtensionNames(&extensions);
+    assert_eq!(instance_info.sType, VkStructureType::VK_STRUCTURE_TYPE_INSTANCE_CREATE_INFO);
+    assert_eq!(instance_info.pApplicationInfo, &*application_info as *const _);
+    assert_eq!(instance_info.ppEnabledExtensionNames, extensions.as_ptr());
+    assert_eq!(instance_info.enabledExtensionCount, 1);
+    let _: &VkInstanceCreateInfo = &instance_info;
+
+    let code = [0x07230203u32, 0x00010000];
+    let shader_info = VkShaderModuleCreateInfo::builder().pCode(&code);
+    assert_eq!(shader_info.pCode, code.as_ptr());
+    assert_eq!(shader_info.codeSize, 8);
+
+    let mut features = VkPhysicalDeviceFeatures2::default();
+    features.features.samplerAnisotropy = TRUE;
+    let mut features11 = VkPhysicalDeviceVulkan11Features::default();
+    let mut features12 = VkPhysicalDeviceVulkan12Features::default();
+
+    let device_info = VkDeviceCreateInfo::builder()
+        .push_next(&mut features)
+        .push_next(&mut features11)
+        .push_next(&mut features12);
+
+    assert_eq!(device_info.sType, VkStructureType::VK_STRUCTURE_TYPE_DEVICE_CREATE_INFO);
+    let next = device_info.pNext;
+    // the chain is borrowed as long as the builder is alive
+    drop(device_info);
+
+    assert_eq!(next, &features12 as *const _ as *const c_void);
+    assert_eq!(features12.pNext, &mut features11 as *mut _ as *mut c_void);
+    assert_eq!(features11.pNext, &mut features as *mut _ as *mut c_void);
+    assert!(features.pNext.is_null());
+}
@@ -0,0 +1,57 @@
+#![deny(warnings)]
+/**
+ * Only the commands and enums of the enabled versions and extensions
+ * should be generated, versions should enable the previous versions
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+
+extern "system" fn get_texture_handle(texture: GLuint) -> GLuint64 {
+    texture as GLuint64 + 1
+}
+
+extern "system" fn clear(_: GLbitfield) {}
+
+// resolves to the crate instead, if the disabled command is generated
+// the glob imports are ambiguous and the test does not compile
+mod missing {
+    #![allow(non_upper_case_globals)]
+    pub const GenVertexArrays: bool = false;
+}
+
+mod probe {
+    pub use missing::*;
+    #[allow(unused_imports)]
+    pub use gl::*;
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glGetTextureHandleARB" => get_texture_handle as *const c_void,
+        "glClear" => clear as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    assert!(gl::is_loaded::Clear());
+    assert!(gl::is_loaded::GetTextureHandleARB());
+
+    unsafe {
+        // GL_VERSION_1_0 through GL_VERSION_2_0
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        assert!(!gl::is_loaded::CreateShader());
+
+        // GL_ARB_bindless_texture
+        assert_eq!(gl::GetTextureHandleARB(41), 42);
+    }
+
+    assert_eq!(gl::UNSIGNED_INT64_ARB, 0x140F);
+
+    // GL_VERSION_3_0
+    assert!(!probe::GenVertexArrays);
+}
@@ -0,0 +1,25 @@
+#![deny(warnings)]
+/**
+ * Extensions should be enabled by default and types using constants
+ * of an extension as array length should build without the extension
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_global_priority,VK_EXT_shader_module_identifier" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build --manifest-path glad-vulkan/Cargo.toml --no-default-features --features VK_VERSION_1_0 && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk;
+
+fn main() {
+    assert_eq!(vk::MAX_GLOBAL_PRIORITY_SIZE_KHR, 16);
+    assert_eq!(vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT, 32);
+
+    let properties: vk::VkQueueFamilyGlobalPriorityPropertiesKHR = unsafe { std::mem::zeroed() };
+    assert_eq!(properties.priorities.len(), vk::MAX_GLOBAL_PRIORITY_SIZE_KHR as usize);
+
+    let identifier: vk::VkShaderModuleIdentifierEXT = unsafe { std::mem::zeroed() };
+    assert_eq!(identifier.identifier.len(), vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT as usize);
+
+    // commands of the extensions are enabled by default
+    assert!(!vk::is_loaded::GetShaderModuleIdentifierEXT());
+}
@@ -0,0 +1,29 @@
+#![deny(warnings)]
+/**
+ * Constants should be typed after the type specified on the enum
+ * and expressions like `(~0ULL)` should be evaluated in that type
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_surface,VK_KHR_external_memory" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk;
+
+fn main() {
+    let whole_size: u64 = vk::WHOLE_SIZE;
+    assert_eq!(whole_size, u64::MAX);
+
+    let ignored: u32 = vk::QUEUE_FAMILY_IGNORED;
+    assert_eq!(ignored, u32::MAX);
+    assert_eq!(vk::QUEUE_FAMILY_EXTERNAL, !1u32);
+    assert_eq!(vk::QUEUE_FAMILY_EXTERNAL_KHR, vk::QUEUE_FAMILY_EXTERNAL);
+
+    let lod_clamp_none: f32 = vk::LOD_CLAMP_NONE;
+    assert_eq!(lod_clamp_none, 1000.0);
+
+    let max_extension_name: [u8; vk::MAX_EXTENSION_NAME_SIZE as usize] = [0; 256];
+    assert_eq!(max_extension_name.len(), 256);
+
+    assert_eq!(vk::KHR_SURFACE_EXTENSION_NAME, "VK_KHR_surface\0");
+}
@@ -0,0 +1,25 @@
+#![deny(warnings)]
+/**
+ * Casts should type constants with the type cast to
+ * and unsigned long long constants should be 64 bit wide
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="egl=" --extensions="EGL_ANDROID_get_frame_timestamps" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_egl;
+use glad_egl::egl;
+
+fn main() {
+    let dont_care: egl::EGLint = egl::DONT_CARE;
+    assert_eq!(dont_care, -1);
+
+    let no_context: egl::EGLContext = egl::NO_CONTEXT;
+    assert!(no_context.is_null());
+
+    let pending: egl::EGLnsecsANDROID = egl::TIMESTAMP_PENDING_ANDROID;
+    assert_eq!(pending, -2);
+
+    let forever: u64 = egl::FOREVER;
+    assert_eq!(forever, u64::MAX);
+}
@@ -0,0 +1,71 @@
+#![deny(warnings)]
+/**
+ * Entry, instance and device tables should load their commands through
+ * vkGetInstanceProcAddr and vkGetDeviceProcAddr, one table per device
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::{self, VkDevice, VkInstance, VkResult};
+use std::ffi::CStr;
+use std::mem::transmute;
+use std::os::raw::{c_char, c_void};
+use std::sync::Mutex;
+
+static INSTANCE_REQUESTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+extern "system" fn enumerate_instance_version(version: *mut u32) -> VkResult {
+    unsafe { *version = 42 };
+    VkResult::VK_SUCCESS
+}
+
+extern "system" fn device_wait_idle_a(_: VkDevice) -> VkResult {
+    VkResult::VK_SUCCESS
+}
+
+extern "system" fn device_wait_idle_b(_: VkDevice) -> VkResult {
+    VkResult::VK_ERROR_DEVICE_LOST
+}
+
+extern "system" fn get_device_proc_addr(device: VkDevice, name: *const c_char) -> *const c_void {
+    let name = unsafe { CStr::from_ptr(name) }.to_str().unwrap();
+    match (unsafe { transmute::<_, usize>(device) }, name) {
+        (0xA, "vkDeviceWaitIdle") => device_wait_idle_a as *const c_void,
+        (0xB, "vkDeviceWaitIdle") => device_wait_idle_b as *const c_void,
+        _ => std::ptr::null(),
+    }
+}
+
+extern "system" fn get_instance_proc_addr(_: VkInstance, name: *const c_char) -> *const c_void {
+    let name = unsafe { CStr::from_ptr(name) }.to_str().unwrap();
+    INSTANCE_REQUESTS.lock().unwrap().push(name.to_string());
+    match name {
+        "vkEnumerateInstanceVersion" => enumerate_instance_version as *const c_void,
+        "vkGetDeviceProcAddr" => get_device_proc_addr as *const c_void,
+        _ => std::ptr::null(),
+    }
+}
+
+fn main() {
+    unsafe {
+        let entry = vk::Entry::from_get_instance_proc_addr(get_instance_proc_addr as *const c_void);
+
+        let mut version = 0;
+        assert_eq!(entry.EnumerateInstanceVersion(&mut version), VkResult::VK_SUCCESS);
+        assert_eq!(version, 42);
+
+        let instance = entry.load_instance(transmute(1usize));
+        assert_eq!(transmute::<_, usize>(instance.handle()), 1);
+
+        let device_a = instance.load_device(transmute(0xAusize));
+        let device_b = instance.load_device(transmute(0xBusize));
+        assert_eq!(device_a.DeviceWaitIdle(device_a.handle()), VkResult::VK_SUCCESS);
+        assert_eq!(device_b.DeviceWaitIdle(device_b.handle()), VkResult::VK_ERROR_DEVICE_LOST);
+    }
+
+    let requests = INSTANCE_REQUESTS.lock().unwrap();
+    assert!(requests.iter().any(|name| name == "vkCreateDevice"));
+    assert!(!requests.iter().any(|name| name == "vkDeviceWaitIdle"));
+}
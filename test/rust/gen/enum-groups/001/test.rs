@@ -0,0 +1,54 @@
+#![deny(warnings)]
+/**
+ * Enum parameters should be typed with their group, group values
+ * should pass the raw value and print their name
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --enum-groups
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static TARGET: AtomicUsize = AtomicUsize::new(0);
+static MASK: AtomicUsize = AtomicUsize::new(0);
+
+extern "system" fn bind_buffer(target: GLenum, _: GLuint) {
+    TARGET.store(target as usize, Ordering::SeqCst);
+}
+
+extern "system" fn clear(mask: GLbitfield) {
+    MASK.store(mask as usize, Ordering::SeqCst);
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glBindBuffer" => bind_buffer as *const c_void,
+        "glClear" => clear as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    unsafe {
+        gl::BindBuffer(gl::BufferTargetARB::ARRAY_BUFFER, 1);
+        assert_eq!(TARGET.load(Ordering::SeqCst), gl::ARRAY_BUFFER as usize);
+
+        // raw values are still accepted explicitly
+        gl::BindBuffer(gl::BufferTargetARB::from_raw(gl::ELEMENT_ARRAY_BUFFER), 1);
+        assert_eq!(TARGET.load(Ordering::SeqCst), gl::ELEMENT_ARRAY_BUFFER as usize);
+
+        gl::Clear(gl::ClearBufferMask::COLOR_BUFFER_BIT | gl::ClearBufferMask::DEPTH_BUFFER_BIT);
+        assert_eq!(MASK.load(Ordering::SeqCst), (gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT) as usize);
+    }
+
+    let mask = gl::ClearBufferMask::COLOR_BUFFER_BIT | gl::ClearBufferMask::STENCIL_BUFFER_BIT;
+    assert!(mask.contains(gl::ClearBufferMask::STENCIL_BUFFER_BIT));
+    assert!(!mask.contains(gl::ClearBufferMask::DEPTH_BUFFER_BIT));
+    assert_eq!(GLbitfield::from(mask), gl::COLOR_BUFFER_BIT | gl::STENCIL_BUFFER_BIT);
+
+    assert_eq!(format!("{:?}", gl::PrimitiveType::TRIANGLES), "PrimitiveType(TRIANGLES)");
+    assert_eq!(format!("{:?}", gl::PrimitiveType::from_raw(0xdead)), "PrimitiveType(0xdead)");
+    assert_eq!(format!("{:?}", mask), "ClearBufferMask(COLOR_BUFFER_BIT | STENCIL_BUFFER_BIT)");
+}
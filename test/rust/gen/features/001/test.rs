@@ -0,0 +1,22 @@
+#![deny(warnings)]
+/**
+ * Version and extension information should be queryable and
+ * report nothing when loading without a current context
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+
+fn main() {
+    gl::load(|_| std::ptr::null());
+
+    assert_eq!(gl::version(), (0, 0));
+    assert!(gl::version() < (4, 5));
+    assert!(!gl::features().VERSION_1_0);
+    assert!(!gl::has_extension(gl::Extension::ARB_debug_output));
+    assert!(!gl::extensions().ARB_debug_output);
+    assert_eq!(gl::Extension::ARB_debug_output.name(), "GL_ARB_debug_output");
+}
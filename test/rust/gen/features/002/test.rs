@@ -0,0 +1,19 @@
+#![deny(warnings)]
+/**
+ * Version and extension information should be available on the context
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --mx
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+
+fn main() {
+    let gl = gl::load(|_| std::ptr::null());
+
+    assert_eq!(gl.version(), (0, 0));
+    assert!(!gl.features().VERSION_4_5);
+    assert!(!gl.has_extension(gl::Extension::ARB_debug_output));
+    assert_eq!(gl.extensions(), gl::Extensions::default());
+}
@@ -0,0 +1,44 @@
+#![deny(warnings)]
+/**
+ * Function pointer types are nullable, structs containing them
+ * can be zero initialized and callbacks can be left out
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_EXT_debug_utils" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::types::*;
+use std::os::raw::c_void;
+
+extern "system" fn callback(
+    _: VkDebugUtilsMessageSeverityFlagBitsEXT,
+    _: VkDebugUtilsMessageTypeFlagsEXT,
+    _: *const VkDebugUtilsMessengerCallbackDataEXT,
+    user_data: *mut c_void
+) -> VkBool32 {
+    unsafe { *(user_data as *mut u32) += 1 };
+    0
+}
+
+fn main() {
+    let allocator = VkAllocationCallbacks::default();
+    assert!(allocator.pfnAllocation.is_none());
+    assert!(allocator.pfnInternalAllocation.is_none());
+
+    let mut calls = 0u32;
+    let info = VkDebugUtilsMessengerCreateInfoEXT::builder()
+        .pfnUserCallback(Some(callback))
+        .pUserData(&mut calls as *mut u32 as *mut c_void);
+
+    let pfn = info.pfnUserCallback.expect("callback is set");
+    unsafe {
+        pfn(
+            VkDebugUtilsMessageSeverityFlagBitsEXT::VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT,
+            VkDebugUtilsMessageTypeFlagsEXT::empty(),
+            std::ptr::null(),
+            info.pUserData
+        );
+    }
+    assert_eq!(calls, 1);
+}
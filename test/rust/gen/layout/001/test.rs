@@ -0,0 +1,15 @@
+#![deny(warnings)]
+/**
+ * The layout tests compare the GLX and GL types, the GL header
+ * is generated as well since the GLX header includes it
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="glx=" rust --layout-tests
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run && cd glad-glx && cargo test --test layout
+ */
+extern crate glad_glx;
+use glad_glx::glx;
+
+fn main() {
+    assert_eq!(std::mem::size_of::<glx::Status>(), std::mem::size_of::<std::ffi::c_int>());
+}
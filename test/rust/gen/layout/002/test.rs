@@ -0,0 +1,21 @@
+#![deny(warnings)]
+/**
+ * Structs which are not typedef'd in the EGL header
+ * are compared as well
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="egl=" --extensions="EGL_HI_clientpixmap" rust --layout-tests
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run && cd glad-egl && cargo test --test layout
+ */
+extern crate glad_egl;
+use glad_egl::egl;
+
+fn main() {
+    let pixmap = egl::EGLClientPixmapHI {
+        pData: std::ptr::null(),
+        iWidth: 1,
+        iHeight: 2,
+        iStride: 4
+    };
+    assert_eq!(pixmap.iStride, 4);
+}
@@ -0,0 +1,71 @@
+#![deny(warnings)]
+/**
+ * Calls of the mocks should be recorded, installed handlers compute
+ * the results and without a handler commands return zero,
+ * handlers may call commands and replace their own handler
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --mock
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::mock;
+use gl::types::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn main() {
+    mock::load();
+    assert!(gl::is_loaded::BindBuffer());
+    // loading queries the version of the context
+    assert_eq!(mock::take_calls()[0].name, "glGetString");
+
+    let bound = Arc::new(AtomicUsize::new(0));
+    let bound_handler = bound.clone();
+    mock::BindBuffer.set(move |target, buffer| {
+        assert_eq!(target, gl::ARRAY_BUFFER);
+        bound_handler.store(buffer as usize, Ordering::Relaxed);
+    });
+    mock::GetError.set(|| gl::INVALID_OPERATION);
+
+    unsafe {
+        gl::BindBuffer(gl::ARRAY_BUFFER, 7);
+        assert_eq!(gl::GetError(), gl::INVALID_OPERATION);
+        assert_eq!(gl::IsBuffer(7), 0);
+        assert!(gl::GetString(gl::VERSION).is_null());
+    }
+
+    assert_eq!(bound.load(Ordering::Relaxed), 7);
+
+    let calls = mock::take_calls();
+    let names: Vec<_> = calls.iter().map(|call| call.name).collect();
+    assert_eq!(names, ["glBindBuffer", "glGetError", "glIsBuffer", "glGetString"]);
+    assert_eq!(calls[0].args, [gl::ARRAY_BUFFER.to_string(), "7".to_string()]);
+    assert!(mock::calls().is_empty());
+
+    mock::GetError.unset();
+    assert_eq!(unsafe { gl::GetError() }, gl::NO_ERROR as GLenum);
+
+    // a handler reporting its error once, through another command
+    mock::IsBuffer.set(|buffer| {
+        mock::GetError.set(|| gl::INVALID_VALUE);
+        mock::IsBuffer.unset();
+        unsafe { gl::IsEnabled(gl::BLEND) };
+        (buffer == 7) as GLboolean
+    });
+
+    unsafe {
+        assert_eq!(gl::IsBuffer(7), gl::TRUE);
+        assert_eq!(gl::IsBuffer(7), gl::FALSE);
+        assert_eq!(gl::GetError(), gl::INVALID_VALUE);
+    }
+
+    let names: Vec<_> = mock::take_calls().iter().map(|call| call.name).collect();
+    assert_eq!(names, ["glGetError", "glIsBuffer", "glIsEnabled", "glIsBuffer", "glGetError"]);
+
+    mock::reset();
+    unsafe { gl::BindBuffer(gl::ARRAY_BUFFER, 8) };
+    assert_eq!(bound.load(Ordering::Relaxed), 7);
+    assert_eq!(mock::calls().len(), 1);
+}
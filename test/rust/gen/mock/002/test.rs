@@ -0,0 +1,38 @@
+#![deny(warnings)]
+/**
+ * Function pointer lookups should resolve to the mocks,
+ * so the dispatch tables can be loaded from the mock entry point
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="" rust --mock
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::{self, mock, VkResult};
+use std::mem::transmute;
+
+fn main() {
+    mock::EnumerateInstanceVersion.set(|version| {
+        unsafe { *version = 42 };
+        VkResult::VK_SUCCESS
+    });
+    mock::DeviceWaitIdle.set(|_| VkResult::VK_ERROR_DEVICE_LOST);
+
+    unsafe {
+        let entry = vk::Entry::from_get_instance_proc_addr(mock::loadfn("vkGetInstanceProcAddr"));
+
+        let mut version = 0;
+        assert_eq!(entry.EnumerateInstanceVersion(&mut version), VkResult::VK_SUCCESS);
+        assert_eq!(version, 42);
+
+        let instance = entry.load_instance(transmute(1usize));
+        let device = instance.load_device(transmute(2usize));
+        assert_eq!(device.DeviceWaitIdle(device.handle()), VkResult::VK_ERROR_DEVICE_LOST);
+        assert_eq!(instance.DestroyInstance(instance.handle(), std::ptr::null()), ());
+    }
+
+    let calls = mock::take_calls();
+    let last = calls.iter().rev().map(|call| call.name).take(2).collect::<Vec<_>>();
+    assert_eq!(last, ["vkDestroyInstance", "vkDeviceWaitIdle"]);
+    assert!(calls.iter().any(|call| call.name == "vkGetDeviceProcAddr"));
+}
@@ -0,0 +1,36 @@
+#![deny(warnings)]
+/**
+ * Mocks of commands named like an enum group should take the group types,
+ * a panicking handler should unwind out of the command instead of aborting
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --mock --enum-groups
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::mock;
+use gl::types::LogicOp;
+use std::panic;
+
+fn main() {
+    mock::load();
+    mock::take_calls();
+
+    mock::LogicOp.set(|opcode| assert_eq!(opcode, LogicOp::XOR));
+    unsafe { gl::LogicOp(LogicOp::XOR) };
+    assert_eq!(mock::take_calls()[0].args, [format!("{:?}", LogicOp::XOR)]);
+
+    // silence the report of the expected panic
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| unsafe { gl::LogicOp(LogicOp::COPY) });
+    let _ = panic::take_hook();
+
+    let payload = result.expect_err("the handler should panic");
+    let message = payload.downcast_ref::<String>().expect("assertion message");
+    assert!(message.contains("assertion `left == right` failed"), "{}", message);
+
+    // the handler is still installed and the next call does not panic
+    unsafe { gl::LogicOp(LogicOp::XOR) };
+    assert_eq!(mock::take_calls().len(), 2);
+}
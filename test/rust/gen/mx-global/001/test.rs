@@ -0,0 +1,60 @@
+#![deny(warnings)]
+/**
+ * The free functions should dispatch through the context current on
+ * the calling thread, dropping the guard restores the previous context
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --mx --mx-global
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+
+extern "system" fn get_error_1() -> GLenum { 1 }
+extern "system" fn get_error_2() -> GLenum { 2 }
+
+fn main() {
+    let gl1 = gl::load(|name| match name {
+        "glGetError" => get_error_1 as *const c_void,
+        _ => std::ptr::null(),
+    });
+    let gl2 = gl::load(|name| match name {
+        "glGetError" => get_error_2 as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    assert!(!gl::has_current());
+    assert!(!gl::is_loaded::GetError());
+    assert_eq!(unsafe { gl::try_::GetError() }, Err(gl::NotLoaded { name: "glGetError" }));
+
+    {
+        let _current = gl::make_current(&gl1);
+        assert!(gl::has_current());
+        assert_eq!(unsafe { gl::GetError() }, 1);
+
+        {
+            let _current = gl::make_current(&gl2);
+            assert_eq!(unsafe { gl::GetError() }, 2);
+            assert_eq!(unsafe { gl::try_::GetError() }, Ok(2));
+            assert!(!gl::is_loaded::Clear());
+        }
+
+        assert_eq!(unsafe { gl::GetError() }, 1);
+
+        // the current context is per thread
+        std::thread::spawn(|| assert!(!gl::has_current())).join().unwrap();
+    }
+
+    assert!(!gl::has_current());
+    assert_eq!(gl::version(), (0, 0));
+
+    // dropping the guards out of order keeps the context of the remaining guard current
+    let current1 = gl::make_current(&gl1);
+    let current2 = gl::make_current(&gl2);
+    drop(current1);
+    assert_eq!(unsafe { gl::GetError() }, 2);
+    drop(current2);
+    assert!(!gl::has_current());
+}
@@ -0,0 +1,39 @@
+#![deny(warnings)]
+/**
+ * Enums should be newtypes which can hold values unknown to the specification,
+ * members and aliases should be available as constants
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="VK_KHR_surface,VK_KHR_maintenance1" rust --mock --newtype-enums
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::{self, mock, VkColorSpaceKHR, VkFormat, VkResult};
+use std::mem::{size_of, transmute};
+
+const VK_ERROR_UNKNOWN_TO_THE_SPECIFICATION: i32 = -1000999000;
+
+fn main() {
+    assert_eq!(size_of::<VkResult>(), size_of::<i32>());
+    assert_eq!(VkResult::VK_ERROR_DEVICE_LOST.as_raw(), -4);
+    assert_eq!(VkFormat::from_raw(37), VkFormat::VK_FORMAT_R8G8B8A8_UNORM);
+    assert_eq!(VkResult::VK_ERROR_OUT_OF_POOL_MEMORY_KHR, VkResult::VK_ERROR_OUT_OF_POOL_MEMORY);
+    assert_eq!(VkColorSpaceKHR::VK_COLORSPACE_SRGB_NONLINEAR_KHR, VkColorSpaceKHR::VK_COLOR_SPACE_SRGB_NONLINEAR_KHR);
+
+    assert_eq!(format!("{:?}", VkResult::VK_ERROR_OUT_OF_POOL_MEMORY_KHR), "VK_ERROR_OUT_OF_POOL_MEMORY");
+    assert_eq!(format!("{:?}", VkFormat::from_raw(1000999000)), "VkFormat(1000999000)");
+
+    mock::DeviceWaitIdle.set(|_| VkResult(VK_ERROR_UNKNOWN_TO_THE_SPECIFICATION));
+
+    let result = unsafe {
+        let entry = vk::Entry::from_get_instance_proc_addr(mock::loadfn("vkGetInstanceProcAddr"));
+        let device = entry.load_instance(transmute(1usize)).load_device(transmute(2usize));
+        device.DeviceWaitIdle(device.handle())
+    };
+
+    match result {
+        VkResult::VK_SUCCESS | VkResult::VK_ERROR_DEVICE_LOST => panic!("unexpected {:?}", result),
+        VkResult(value) => assert_eq!(value, VK_ERROR_UNKNOWN_TO_THE_SPECIFICATION),
+    }
+    assert_eq!(format!("{:?}", result), "VkResult(-1000999000)");
+}
@@ -0,0 +1,55 @@
+#![deny(warnings)]
+#![no_std]
+#![no_main]
+/**
+ * A no_std crate with global functions should build for a target without
+ * the standard library and link into a binary which provides its own panic handler
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gles2=" rust --no-std
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build --manifest-path glad-gles2/Cargo.toml --target thumbv7em-none-eabihf && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gles2;
+use core::ffi::{c_char, c_int, c_void};
+use core::sync::atomic::{AtomicU32, Ordering};
+use glad_gles2::gl;
+use gl::types::*;
+
+#[link(name = "c")]
+extern "C" {
+    fn abort() -> !;
+}
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    unsafe { abort() }
+}
+
+// the precompiled core references the personality routine even with panic = "abort"
+#[no_mangle]
+extern "C" fn rust_eh_personality() {}
+
+static MASK: AtomicU32 = AtomicU32::new(0);
+
+extern "system" fn clear(mask: GLbitfield) {
+    MASK.store(mask, Ordering::SeqCst);
+}
+
+#[no_mangle]
+pub extern "C" fn main(_: c_int, _: *const *const c_char) -> c_int {
+    gl::load(|name| match name {
+        "glClear" => clear as *const c_void,
+        _ => core::ptr::null(),
+    });
+
+    assert!(gl::is_loaded::Clear());
+    assert!(!gl::is_loaded::BindBuffer());
+
+    unsafe {
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        assert!(gl::try_::BindBuffer(gl::ARRAY_BUFFER, 0).is_err());
+    }
+
+    assert_eq!(MASK.load(Ordering::SeqCst), gl::COLOR_BUFFER_BIT);
+    0
+}
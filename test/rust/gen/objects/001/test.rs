@@ -0,0 +1,85 @@
+#![deny(warnings)]
+/**
+ * Owned objects should delete their names when dropped,
+ * also on early returns, unless the name is released
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --objects
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::objects::{Buffer, Shader};
+use gl::types::*;
+use std::os::raw::c_void;
+use std::sync::Mutex;
+
+static NEXT: Mutex<GLuint> = Mutex::new(1);
+static DELETED: Mutex<Vec<GLuint>> = Mutex::new(Vec::new());
+
+extern "system" fn gen_buffers(n: GLsizei, buffers: *mut GLuint) {
+    let mut next = NEXT.lock().unwrap();
+    for i in 0..n as usize {
+        unsafe { *buffers.add(i) = *next };
+        *next += 1;
+    }
+}
+
+extern "system" fn delete_buffers(n: GLsizei, buffers: *const GLuint) {
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, n as usize) };
+    DELETED.lock().unwrap().extend_from_slice(buffers);
+}
+
+extern "system" fn create_shader(type_: GLenum) -> GLuint {
+    assert_eq!(type_, gl::VERTEX_SHADER);
+    100
+}
+
+extern "system" fn delete_shader(shader: GLuint) {
+    DELETED.lock().unwrap().push(shader);
+}
+
+fn deleted() -> Vec<GLuint> {
+    std::mem::take(&mut *DELETED.lock().unwrap())
+}
+
+fn upload(fail: bool) -> Result<Buffer, String> {
+    let buffer = unsafe { Buffer::new() };
+    if fail {
+        return Err(format!("unable to upload into buffer {}", buffer.name()));
+    }
+    Ok(buffer)
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glGenBuffers" => gen_buffers as *const c_void,
+        "glDeleteBuffers" => delete_buffers as *const c_void,
+        "glCreateShader" => create_shader as *const c_void,
+        "glDeleteShader" => delete_shader as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    let buffer = upload(false).unwrap();
+    assert_eq!(buffer.name(), 1);
+    assert!(deleted().is_empty());
+
+    assert_eq!(upload(true).unwrap_err(), "unable to upload into buffer 2");
+    assert_eq!(deleted(), [2]);
+
+    drop(buffer);
+    assert_eq!(deleted(), [1]);
+
+    let shader = unsafe { Shader::new(gl::VERTEX_SHADER) };
+    assert_eq!(shader.name(), 100);
+    drop(shader);
+    assert_eq!(deleted(), [100]);
+
+    // released names are not deleted
+    let name = unsafe { Buffer::new() }.into_raw();
+    assert_eq!(name, 3);
+    assert!(deleted().is_empty());
+
+    drop(unsafe { Buffer::from_raw(name) });
+    assert_eq!(deleted(), [3]);
+}
@@ -0,0 +1,76 @@
+#![deny(warnings)]
+/**
+ * Owned handles should be destroyed with their allocation callbacks
+ * when dropped, children before their parents
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="" rust --mock --objects
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::{self, mock, objects};
+use vk::types::*;
+use std::mem::{transmute, zeroed};
+use std::os::raw::c_void;
+use std::ptr;
+
+extern "system" fn allocate(_: *mut c_void, _: usize, _: usize, _: VkSystemAllocationScope) -> *mut c_void {
+    ptr::null_mut()
+}
+
+extern "system" fn reallocate(_: *mut c_void, _: *mut c_void, _: usize, _: usize, _: VkSystemAllocationScope) -> *mut c_void {
+    ptr::null_mut()
+}
+
+extern "system" fn free(_: *mut c_void, _: *mut c_void) {}
+
+fn main() {
+    let allocator = VkAllocationCallbacks {
+        pUserData: ptr::null_mut(),
+        pfnAllocation: Some(allocate),
+        pfnReallocation: Some(reallocate),
+        pfnFree: Some(free),
+        pfnInternalAllocation: None,
+        pfnInternalFree: None,
+    };
+    let address = &allocator as *const _ as usize;
+
+    mock::CreateInstance.set(|_, _, instance| {
+        unsafe { *instance = transmute(1usize) };
+        VkResult::VK_SUCCESS
+    });
+    mock::CreateDevice.set(|_, _, _, device| {
+        unsafe { *device = transmute(2usize) };
+        VkResult::VK_SUCCESS
+    });
+    mock::CreateCommandPool.set(move |_, _, allocator, pool| {
+        assert_eq!(allocator as usize, address);
+        unsafe { *pool = transmute(3u64) };
+        VkResult::VK_SUCCESS
+    });
+    mock::CreateBuffer.set(|_, _, _, _| VkResult::VK_ERROR_OUT_OF_DEVICE_MEMORY);
+    mock::DestroyCommandPool.set(move |_, _, allocator| assert_eq!(allocator as usize, address));
+
+    unsafe {
+        let entry = vk::Entry::from_get_instance_proc_addr(mock::loadfn("vkGetInstanceProcAddr"));
+        let instance = objects::Instance::CreateInstance(&entry, &zeroed(), None).unwrap();
+        let device = objects::Device::CreateDevice(&instance, transmute(5usize), &zeroed(), None).unwrap();
+
+        let pool = objects::CommandPool::CreateCommandPool(&device, &zeroed(), Some(&allocator)).unwrap();
+        assert_eq!(transmute::<_, u64>(pool.handle()), 3);
+        let _buffer = objects::CommandBuffer::from_raw(&pool, transmute(4usize));
+
+        match objects::Buffer::CreateBuffer(&device, &zeroed(), None) {
+            Err(error) => assert_eq!(error, VkResult::VK_ERROR_OUT_OF_DEVICE_MEMORY),
+            Ok(_) => panic!("buffer created"),
+        }
+
+        // released handles are not destroyed
+        let fence = objects::Fence::from_raw(&device, transmute(6u64), None);
+        assert_eq!(transmute::<_, u64>(fence.into_raw()), 6);
+        mock::take_calls();
+    }
+
+    let destroyed = mock::take_calls().iter().map(|call| call.name).collect::<Vec<_>>();
+    assert_eq!(destroyed, ["vkFreeCommandBuffers", "vkDestroyCommandPool", "vkDestroyDevice", "vkDestroyInstance"]);
+}
@@ -0,0 +1,48 @@
+#![deny(warnings)]
+/**
+ * Functions should only be resolved on first use, once,
+ * and fall back to their aliases
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --on-demand
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static REQUESTED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+extern "system" fn dummy(_: gl::types::GLenum) {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn requested(name: &str) -> usize {
+    REQUESTED.lock().unwrap().iter().filter(|&&requested| requested == name).count()
+}
+
+fn main() {
+    gl::load(|name| {
+        REQUESTED.lock().unwrap().push(name);
+        match name {
+            "glClear" | "glActiveTextureARB" => dummy as *const std::os::raw::c_void,
+            _ => std::ptr::null(),
+        }
+    });
+
+    assert_eq!(requested("glClear"), 0);
+
+    unsafe {
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+    }
+    assert_eq!(requested("glClear"), 1);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+    unsafe { gl::ActiveTexture(gl::TEXTURE0) };
+    assert_eq!(requested("glActiveTexture"), 1);
+    assert_eq!(requested("glActiveTextureARB"), 1);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,39 @@
+#![deny(warnings)]
+/**
+ * The loader should not be called with the lock held, a loader which
+ * checks other commands must not deadlock
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --on-demand
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+extern "system" fn dummy(_: gl::types::GLenum) {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn main() {
+    gl::load(|name| {
+        // the loader itself can not resolve functions through itself
+        if name == "glClear" {
+            assert!(!gl::is_loaded::Enable());
+        }
+        match name {
+            "glClear" | "glEnable" => dummy as *const c_void,
+            _ => std::ptr::null(),
+        }
+    });
+
+    let handle = std::thread::spawn(|| unsafe { gl::Enable(gl::BLEND) });
+    unsafe { gl::Clear(gl::COLOR_BUFFER_BIT) };
+    handle.join().unwrap();
+
+    assert!(gl::is_loaded::Enable());
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
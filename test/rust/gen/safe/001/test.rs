@@ -0,0 +1,69 @@
+#![deny(warnings)]
+/**
+ * Safe wrappers should derive counts from slices, pass strings
+ * with their lengths, take null-terminated strings as CStr and return output strings
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --safe
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::sync::Mutex;
+
+static SOURCES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+extern "system" fn shader_source(shader: GLuint, count: GLsizei, string: *const *const GLchar, length: *const GLint) {
+    assert_eq!(shader, 7);
+    let mut sources = SOURCES.lock().unwrap();
+    for i in 0..count as usize {
+        let source = unsafe { std::slice::from_raw_parts(*string.add(i) as *const u8, *length.add(i) as usize) };
+        sources.push(String::from_utf8(source.to_vec()).unwrap());
+    }
+}
+
+extern "system" fn get_shader_info_log(_: GLuint, buf_size: GLsizei, length: *mut GLsizei, info_log: *mut GLchar) {
+    let log = b"0:1: error";
+    assert!(buf_size as usize >= log.len());
+    unsafe {
+        std::ptr::copy_nonoverlapping(log.as_ptr(), info_log as *mut u8, log.len());
+        *length = log.len() as GLsizei;
+    }
+}
+
+extern "system" fn gen_buffers(n: GLsizei, buffers: *mut GLuint) {
+    for i in 0..n as usize {
+        unsafe { *buffers.add(i) = i as GLuint + 1 };
+    }
+}
+
+extern "system" fn get_uniform_location(_: GLuint, name: *const GLchar) -> GLint {
+    let name = unsafe { std::ffi::CStr::from_ptr(name) };
+    name.to_bytes().len() as GLint
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glShaderSource" => shader_source as *const c_void,
+        "glGetShaderInfoLog" => get_shader_info_log as *const c_void,
+        "glGenBuffers" => gen_buffers as *const c_void,
+        "glGetUniformLocation" => get_uniform_location as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    unsafe {
+        gl::safe::ShaderSource(7, &["#version 330\n", "void main() {}"]);
+        assert_eq!(*SOURCES.lock().unwrap(), ["#version 330\n", "void main() {}"]);
+
+        assert_eq!(gl::safe::GetShaderInfoLog(7, 512), "0:1: error");
+
+        let mut buffers = [0; 3];
+        gl::safe::GenBuffers(&mut buffers);
+        assert_eq!(buffers, [1, 2, 3]);
+
+        assert_eq!(gl::safe::GetUniformLocation(7, CStr::from_bytes_with_nul(b"model\0").unwrap()), 5);
+    }
+}
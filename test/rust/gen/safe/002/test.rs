@@ -0,0 +1,50 @@
+#![deny(warnings)]
+/**
+ * Safe wrappers should enumerate through the two call idiom
+ * and retry if the result is incomplete
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="vulkan=" --extensions="" rust --safe
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_vulkan;
+use glad_vulkan::vk::{self, VkExtensionProperties, VkResult};
+use std::os::raw::c_char;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static EXTENSIONS: AtomicUsize = AtomicUsize::new(1);
+
+extern "system" fn enumerate_instance_extension_properties(layer: *const c_char, count: *mut u32, properties: *mut VkExtensionProperties) -> VkResult {
+    assert!(layer.is_null());
+    // an extension shows up between the two calls of the first attempt
+    let available = EXTENSIONS.load(Ordering::SeqCst);
+    unsafe {
+        if properties.is_null() {
+            *count = available as u32;
+            EXTENSIONS.store(2, Ordering::SeqCst);
+            return VkResult::VK_SUCCESS;
+        }
+
+        let written = (*count as usize).min(available);
+        for i in 0..written {
+            (*properties.add(i)).specVersion = i as u32 + 1;
+        }
+        *count = written as u32;
+
+        if written < available { VkResult::VK_INCOMPLETE } else { VkResult::VK_SUCCESS }
+    }
+}
+
+fn main() {
+    vk::load(|name| match name {
+        "vkEnumerateInstanceExtensionProperties" => enumerate_instance_extension_properties as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    unsafe {
+        let (result, properties) = vk::safe::EnumerateInstanceExtensionProperties(None);
+        assert_eq!(result, VkResult::VK_SUCCESS);
+        assert_eq!(properties.iter().map(|p| p.specVersion).collect::<Vec<_>>(), [1, 2]);
+    }
+}
@@ -0,0 +1,59 @@
+#![deny(warnings)]
+/**
+ * Calls should be counted and their CPU time measured per command, time
+ * spent blocked is not counted, the most expensive commands are listed first
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --stats
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+use std::time::{Duration, Instant};
+
+extern "system" fn uniform_1f(_: GLint, _: GLfloat) {}
+
+extern "system" fn finish() {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(20) {}
+}
+
+extern "system" fn flush() {
+    std::thread::sleep(Duration::from_millis(20));
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glUniform1f" => uniform_1f as *const c_void,
+        "glFinish" => finish as *const c_void,
+        "glFlush" => flush as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    unsafe {
+        for i in 0..100 {
+            gl::Uniform1f(0, i as GLfloat);
+        }
+        gl::Finish();
+        gl::Flush();
+    }
+
+    let stats = gl::stats::snapshot();
+    assert_eq!(stats.len(), 3);
+
+    assert_eq!(stats[0].name, "glFinish");
+    assert_eq!(stats[0].calls, 1);
+    assert!(stats[0].time >= Duration::from_millis(10));
+    assert!(stats[0].to_string().starts_with("glFinish: 1 calls, "));
+
+    let flush = stats.iter().find(|stats| stats.name == "glFlush").unwrap();
+    assert!(flush.time < Duration::from_millis(10));
+
+    let uniform = stats.iter().find(|stats| stats.name == "glUniform1f").unwrap();
+    assert_eq!(uniform.calls, 100);
+
+    gl::stats::reset();
+    assert!(gl::stats::snapshot().is_empty());
+}
@@ -0,0 +1,105 @@
+#![deny(warnings)]
+/**
+ * Calls should be recorded into a trace with the payloads of their pointers
+ * and replaying the trace should issue the same calls again, besides calls
+ * passing callbacks or pointers without a payload which are not buffer offsets
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --trace
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::ffi::CStr;
+use std::os::raw::c_void;
+use std::sync::Mutex;
+
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record(call: String) {
+    CALLS.lock().unwrap().push(call);
+}
+
+extern "system" fn buffer_data(target: GLenum, size: GLsizeiptr, data: *const c_void, usage: GLenum) {
+    let data = unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) };
+    record(format!("BufferData({}, {:?}, {})", target, data, usage));
+}
+
+extern "system" fn shader_source(shader: GLuint, count: GLsizei, string: *const *const GLchar, length: *const GLint) {
+    assert!(length.is_null());
+    let sources: Vec<_> = (0..count as usize)
+        .map(|i| unsafe { CStr::from_ptr(*string.add(i)) }.to_str().unwrap().to_string())
+        .collect();
+    record(format!("ShaderSource({}, {:?})", shader, sources));
+}
+
+extern "system" fn gen_buffers(n: GLsizei, buffers: *mut GLuint) {
+    for i in 0..n as usize {
+        unsafe { *buffers.add(i) = i as GLuint + 1 };
+    }
+    record(format!("GenBuffers({})", n));
+}
+
+extern "system" fn vertex_attrib_pointer(index: GLuint, size: GLint, type_: GLenum, _: GLboolean, stride: GLsizei, pointer: *const c_void) {
+    record(format!("VertexAttribPointer({}, {}, {}, {}, {:?})", index, size, type_, stride, pointer));
+}
+
+extern "system" fn clear_buffer_data(target: GLenum, _: GLenum, _: GLenum, _: GLenum, _: *const c_void) {
+    record(format!("ClearBufferData({})", target));
+}
+
+extern "system" fn debug_message_callback(_: GLDEBUGPROC, _: *const c_void) {
+    record("DebugMessageCallback".to_string());
+}
+
+extern "system" fn debug_callback(_: GLenum, _: GLenum, _: GLuint, _: GLenum, _: GLsizei, _: *const GLchar, _: *mut c_void) {}
+
+fn main() {
+    gl::load(|name| match name {
+        "glBufferData" => buffer_data as *const c_void,
+        "glShaderSource" => shader_source as *const c_void,
+        "glGenBuffers" => gen_buffers as *const c_void,
+        "glVertexAttribPointer" => vertex_attrib_pointer as *const c_void,
+        "glClearBufferData" => clear_buffer_data as *const c_void,
+        "glDebugMessageCallback" => debug_message_callback as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    let path = std::env::temp_dir().join("glad-trace-001.trace");
+    gl::trace::start(&path).unwrap();
+
+    unsafe {
+        let mut buffers = [0; 2];
+        gl::GenBuffers(2, buffers.as_mut_ptr());
+        assert_eq!(buffers, [1, 2]);
+
+        let data = [1u8, 2, 3, 4];
+        gl::BufferData(gl::ARRAY_BUFFER, 4, data.as_ptr() as *const c_void, gl::STATIC_DRAW);
+
+        let sources = [b"void main() {}\0".as_ptr() as *const GLchar, b"// end\0".as_ptr() as *const GLchar];
+        gl::ShaderSource(3, 2, sources.as_ptr(), std::ptr::null());
+
+        // an offset into the bound buffer
+        gl::VertexAttribPointer(0, 3, gl::FLOAT, gl::FALSE, 12, 16 as *const c_void);
+
+        // the size of the data depends on the format and type
+        let zero = [0u8; 4];
+        gl::ClearBufferData(gl::ARRAY_BUFFER, gl::R8, gl::RED, gl::UNSIGNED_BYTE, zero.as_ptr() as *const c_void);
+
+        gl::DebugMessageCallback(Some(debug_callback), std::ptr::null());
+    }
+
+    gl::trace::stop().unwrap();
+    let recorded = std::mem::take(&mut *CALLS.lock().unwrap());
+    assert_eq!(recorded.len(), 6);
+    assert_eq!(recorded[3], "VertexAttribPointer(0, 3, 5126, 12, 0x10)");
+
+    let summary = unsafe { gl::trace::replay(std::fs::File::open(&path).unwrap()) }.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(summary, gl::trace::Summary { replayed: 4, skipped: 2 });
+    assert_eq!(*CALLS.lock().unwrap(), recorded[..4]);
+
+    assert!(unsafe { gl::trace::replay(&b"GLADTRC1\x03egl"[..]) }.is_err());
+}
@@ -0,0 +1,95 @@
+#![deny(warnings)]
+/**
+ * Object names created by replayed calls should be passed in place of the
+ * recorded names, names of other classes and unknown names as recorded
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --trace
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+static NEXT_NAME: AtomicU32 = AtomicU32::new(1);
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record(call: String) {
+    CALLS.lock().unwrap().push(call);
+}
+
+extern "system" fn gen_buffers(n: GLsizei, buffers: *mut GLuint) {
+    for i in 0..n as usize {
+        unsafe { *buffers.add(i) = NEXT_NAME.fetch_add(1, Ordering::SeqCst) };
+    }
+}
+
+extern "system" fn create_program() -> GLuint {
+    NEXT_NAME.fetch_add(1, Ordering::SeqCst)
+}
+
+extern "system" fn bind_buffer(target: GLenum, buffer: GLuint) {
+    record(format!("BindBuffer({}, {})", target, buffer));
+}
+
+extern "system" fn bind_texture(target: GLenum, texture: GLuint) {
+    record(format!("BindTexture({}, {})", target, texture));
+}
+
+extern "system" fn use_program(program: GLuint) {
+    record(format!("UseProgram({})", program));
+}
+
+extern "system" fn delete_buffers(n: GLsizei, buffers: *const GLuint) {
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, n as usize) };
+    record(format!("DeleteBuffers({:?})", buffers));
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glGenBuffers" => gen_buffers as *const c_void,
+        "glCreateProgram" => create_program as *const c_void,
+        "glBindBuffer" => bind_buffer as *const c_void,
+        "glBindTexture" => bind_texture as *const c_void,
+        "glUseProgram" => use_program as *const c_void,
+        "glDeleteBuffers" => delete_buffers as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    let path = std::env::temp_dir().join("glad-trace-002.trace");
+    gl::trace::start(&path).unwrap();
+
+    unsafe {
+        let mut buffers = [0; 2];
+        gl::GenBuffers(2, buffers.as_mut_ptr());
+        assert_eq!(buffers, [1, 2]);
+        gl::BindBuffer(gl::ARRAY_BUFFER, buffers[1]);
+
+        let program = gl::CreateProgram();
+        assert_eq!(program, 3);
+        gl::UseProgram(program);
+
+        // a texture which was not created while recording
+        gl::BindTexture(gl::TEXTURE_2D, 2);
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        gl::DeleteBuffers(2, buffers.as_ptr());
+    }
+
+    gl::trace::stop().unwrap();
+    CALLS.lock().unwrap().clear();
+
+    let summary = unsafe { gl::trace::replay(std::fs::File::open(&path).unwrap()) }.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(summary, gl::trace::Summary { replayed: 7, skipped: 0 });
+    assert_eq!(*CALLS.lock().unwrap(), [
+        format!("BindBuffer({}, 5)", gl::ARRAY_BUFFER),
+        "UseProgram(6)".to_string(),
+        format!("BindTexture({}, 2)", gl::TEXTURE_2D),
+        format!("BindBuffer({}, 0)", gl::ARRAY_BUFFER),
+        "DeleteBuffers([4, 5])".to_string(),
+    ]);
+}
@@ -0,0 +1,109 @@
+#![deny(warnings)]
+/**
+ * Handles created by replayed calls should be passed in place of the recorded
+ * handles, attribute lists should be replayed up to EGL_NONE and calls passing
+ * native displays other than null should not be replayed
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="egl=" rust --trace
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_egl;
+use glad_egl::egl;
+use egl::types::*;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static NEXT_HANDLE: AtomicUsize = AtomicUsize::new(1);
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record(call: String) {
+    CALLS.lock().unwrap().push(call);
+}
+
+fn handle() -> *mut c_void {
+    (NEXT_HANDLE.fetch_add(1, Ordering::SeqCst) * 0x100) as *mut c_void
+}
+
+fn attribs(mut list: *const EGLint) -> Vec<EGLint> {
+    let mut attribs = Vec::new();
+    unsafe {
+        while *list != egl::NONE as EGLint {
+            attribs.push(*list);
+            list = list.add(1);
+        }
+    }
+    attribs
+}
+
+extern "system" fn get_display(display_id: EGLNativeDisplayType) -> EGLDisplay {
+    record(format!("GetDisplay({:?})", display_id));
+    handle()
+}
+
+extern "system" fn choose_config(dpy: EGLDisplay, attrib_list: *const EGLint, configs: *mut EGLConfig, config_size: EGLint, num_config: *mut EGLint) -> EGLBoolean {
+    assert_eq!(config_size, 1);
+    unsafe {
+        *configs = handle();
+        *num_config = 1;
+    }
+    record(format!("ChooseConfig({:?}, {:?})", dpy, attribs(attrib_list)));
+    egl::TRUE
+}
+
+extern "system" fn create_context(dpy: EGLDisplay, config: EGLConfig, share_context: EGLContext, attrib_list: *const EGLint) -> EGLContext {
+    record(format!("CreateContext({:?}, {:?}, {:?}, {:?})", dpy, config, share_context, attribs(attrib_list)));
+    handle()
+}
+
+extern "system" fn make_current(dpy: EGLDisplay, draw: EGLSurface, read: EGLSurface, ctx: EGLContext) -> EGLBoolean {
+    record(format!("MakeCurrent({:?}, {:?}, {:?}, {:?})", dpy, draw, read, ctx));
+    egl::TRUE
+}
+
+fn main() {
+    egl::load(|name| match name {
+        "eglGetDisplay" => get_display as *const c_void,
+        "eglChooseConfig" => choose_config as *const c_void,
+        "eglCreateContext" => create_context as *const c_void,
+        "eglMakeCurrent" => make_current as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    let path = std::env::temp_dir().join("glad-trace-003.trace");
+    egl::trace::start(&path).unwrap();
+
+    unsafe {
+        let display = egl::GetDisplay(egl::DEFAULT_DISPLAY);
+        assert_eq!(display as usize, 0x100);
+
+        let mut config = std::ptr::null_mut();
+        let mut num_config = 0;
+        let config_attribs = [egl::RED_SIZE as EGLint, 8, egl::NONE as EGLint, 1];
+        egl::ChooseConfig(display, config_attribs.as_ptr(), &mut config, 1, &mut num_config);
+        assert_eq!(config as usize, 0x200);
+
+        let context_attribs = [egl::CONTEXT_MAJOR_VERSION as EGLint, 2, egl::NONE as EGLint];
+        let context = egl::CreateContext(display, config, egl::NO_CONTEXT, context_attribs.as_ptr());
+        assert_eq!(context as usize, 0x300);
+        egl::MakeCurrent(display, egl::NO_SURFACE, egl::NO_SURFACE, context);
+
+        // a native display is only valid in the recording process
+        egl::GetDisplay(0x1234 as EGLNativeDisplayType);
+    }
+
+    egl::trace::stop().unwrap();
+    CALLS.lock().unwrap().clear();
+
+    let summary = unsafe { egl::trace::replay(std::fs::File::open(&path).unwrap()) }.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(summary, egl::trace::Summary { replayed: 4, skipped: 1 });
+    assert_eq!(*CALLS.lock().unwrap(), [
+        "GetDisplay(0x0)".to_string(),
+        format!("ChooseConfig(0x500, [{}, 8])", egl::RED_SIZE),
+        format!("CreateContext(0x500, 0x600, 0x0, [{}, 2])", egl::CONTEXT_MAJOR_VERSION),
+        "MakeCurrent(0x500, 0x0, 0x0, 0x700)".to_string(),
+    ]);
+}
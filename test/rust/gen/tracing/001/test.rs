@@ -0,0 +1,125 @@
+#![deny(warnings)]
+/**
+ * Calls should enter a span named after the command with the arguments and
+ * the return value as fields, with `tracing-gl-errors` errors reported by
+ * glGetError are emitted as events
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --tracing
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+extern crate tracing;
+use glad_gl::gl;
+use gl::types::*;
+use std::fmt::Debug;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+#[derive(Debug, PartialEq)]
+enum Entry {
+    Span(String, Vec<(String, String)>),
+    Event(Level, Vec<(String, String)>),
+}
+
+struct Fields(Vec<(String, String)>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Entry>>>);
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        let mut fields = Fields(Vec::new());
+        span.record(&mut fields);
+        let mut entries = self.0.lock().unwrap();
+        entries.push(Entry::Span(span.metadata().name().to_string(), fields.0));
+        Id::from_u64(entries.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record) {
+        let mut entries = self.0.lock().unwrap();
+        if let Entry::Span(_, ref mut fields) = entries[span.into_u64() as usize - 1] {
+            let mut recorded = Fields(Vec::new());
+            values.record(&mut recorded);
+            fields.extend(recorded.0);
+        }
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut fields = Fields(Vec::new());
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(Entry::Event(*event.metadata().level(), fields.0));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+static ERROR: AtomicU32 = AtomicU32::new(0);
+
+extern "system" fn clear(mask: GLbitfield) {
+    if mask != gl::COLOR_BUFFER_BIT {
+        ERROR.store(gl::INVALID_VALUE, Ordering::SeqCst);
+    }
+}
+
+extern "system" fn is_enabled(_: GLenum) -> GLboolean {
+    gl::TRUE
+}
+
+extern "system" fn get_error() -> GLenum {
+    ERROR.swap(gl::NO_ERROR, Ordering::SeqCst)
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields.iter().map(|&(name, value)| (name.to_string(), value.to_string())).collect()
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glClear" => clear as *const c_void,
+        "glIsEnabled" => is_enabled as *const c_void,
+        "glGetError" => get_error as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || unsafe {
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        assert_eq!(gl::IsEnabled(gl::BLEND), gl::TRUE);
+        gl::Clear(0);
+        ERROR.store(gl::INVALID_ENUM, Ordering::SeqCst);
+        assert_eq!(gl::GetError(), gl::INVALID_ENUM);
+    });
+
+    let entries = recorder.0.lock().unwrap();
+    assert_eq!(*entries, vec![
+        Entry::Span("glClear".to_string(), fields(&[("mask", "16384")])),
+        Entry::Span("glIsEnabled".to_string(), fields(&[("cap", "3042"), ("ret", "1")])),
+        Entry::Span("glClear".to_string(), fields(&[("mask", "0")])),
+        Entry::Event(Level::ERROR, fields(&[
+            ("message", "glClear failed with error 0x0501"),
+            ("command", "\"glClear\""),
+            ("error", "1281"),
+        ])),
+        // the error is not consumed for glGetError itself
+        Entry::Span("glGetError".to_string(), fields(&[("ret", "1280")])),
+    ]);
+}
@@ -0,0 +1,69 @@
+#![deny(warnings)]
+/**
+ * Without `tracing-gl-errors` glGetError should not be called by the
+ * instrumentation, the error flag is left to the application
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --tracing
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+extern crate tracing;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+struct Everything;
+
+impl Subscriber for Everything {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event) {
+        panic!("no events expected");
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+static ERROR: AtomicU32 = AtomicU32::new(0);
+static GET_ERROR_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+extern "system" fn clear(_: GLbitfield) {
+    ERROR.store(gl::INVALID_VALUE, Ordering::SeqCst);
+}
+
+extern "system" fn get_error() -> GLenum {
+    GET_ERROR_CALLS.fetch_add(1, Ordering::SeqCst);
+    ERROR.swap(gl::NO_ERROR, Ordering::SeqCst)
+}
+
+fn main() {
+    gl::load(|name| match name {
+        "glClear" => clear as *const c_void,
+        "glGetError" => get_error as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    tracing::subscriber::with_default(Everything, || unsafe {
+        gl::Clear(0);
+        assert_eq!(GET_ERROR_CALLS.load(Ordering::SeqCst), 0);
+        assert_eq!(gl::GetError(), gl::INVALID_VALUE);
+    });
+
+    assert_eq!(GET_ERROR_CALLS.load(Ordering::SeqCst), 1);
+}
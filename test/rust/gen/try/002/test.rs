@@ -0,0 +1,31 @@
+#![deny(warnings)]
+/**
+ * Checked variants should report missing commands by name
+ * instead of panicking
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --mx
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+
+extern "system" fn clear(_: gl::types::GLbitfield) {}
+
+fn main() {
+    let gl = gl::load(|name| match name {
+        "glClear" => clear as *const std::os::raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    assert!(gl.is_loaded_Clear());
+    assert!(!gl.is_loaded_BindBuffer());
+
+    unsafe {
+        assert_eq!(gl.try_Clear(gl::COLOR_BUFFER_BIT), Ok(()));
+
+        let error = gl.try_BindBuffer(gl::ARRAY_BUFFER, 0).unwrap_err();
+        assert_eq!(error, gl::NotLoaded { name: "glBindBuffer" });
+        assert_eq!(error.to_string(), "gl: function glBindBuffer not loaded");
+    }
+}
@@ -0,0 +1,50 @@
+#![deny(warnings)]
+/**
+ * Commands can be loaded from any thread and unloading
+ * should reset commands, version and extensions
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+
+extern "system" fn get_string(name: GLenum) -> *const GLubyte {
+    match name {
+        gl::VERSION => b"3.3.0\0".as_ptr(),
+        _ => std::ptr::null(),
+    }
+}
+
+extern "system" fn clear(_: GLbitfield) {}
+
+fn loader(name: &'static str) -> *const c_void {
+    match name {
+        "glGetString" => get_string as *const c_void,
+        "glClear" => clear as *const c_void,
+        _ => std::ptr::null(),
+    }
+}
+
+fn main() {
+    std::thread::spawn(|| gl::load(loader)).join().unwrap();
+
+    assert!(gl::is_loaded::Clear());
+    assert_eq!(gl::version(), (3, 3));
+    assert!(gl::features().VERSION_3_3);
+    unsafe { gl::Clear(gl::COLOR_BUFFER_BIT) };
+
+    gl::unload();
+
+    assert!(!gl::is_loaded::Clear());
+    assert!(!gl::is_loaded::GetString());
+    assert_eq!(gl::version(), (0, 0));
+    assert!(!gl::features().VERSION_1_0);
+
+    gl::load(loader);
+    assert!(gl::is_loaded::Clear());
+    assert_eq!(gl::version(), (3, 3));
+}
@@ -0,0 +1,51 @@
+#![deny(warnings)]
+/**
+ * A trace recorded without a context should be replayed by the replay binary
+ * in the headless context it creates
+ *
+ * GLAD: $GLAD --out-path=$tmp --api="gl:core=" rust --trace --loader
+ * COMPILE: cp -r $test_dir/. $tmp && cd $tmp && cargo build
+ * RUN: cargo run && cd glad-gl && cargo run --bin replay -- ../test.trace | grep -x "replayed 5 calls, skipped 0 calls"
+ */
+extern crate glad_gl;
+use glad_gl::gl;
+use gl::types::*;
+use std::os::raw::c_void;
+
+extern "system" fn gen_buffers(n: GLsizei, buffers: *mut GLuint) {
+    for i in 0..n as usize {
+        unsafe { *buffers.add(i) = i as GLuint + 7 };
+    }
+}
+
+extern "system" fn clear_color(_: GLfloat, _: GLfloat, _: GLfloat, _: GLfloat) {}
+extern "system" fn bind_buffer(_: GLenum, _: GLuint) {}
+extern "system" fn buffer_data(_: GLenum, _: GLsizeiptr, _: *const c_void, _: GLenum) {}
+extern "system" fn delete_buffers(_: GLsizei, _: *const GLuint) {}
+
+fn main() {
+    gl::load(|name| match name {
+        "glGenBuffers" => gen_buffers as *const c_void,
+        "glClearColor" => clear_color as *const c_void,
+        "glBindBuffer" => bind_buffer as *const c_void,
+        "glBufferData" => buffer_data as *const c_void,
+        "glDeleteBuffers" => delete_buffers as *const c_void,
+        _ => std::ptr::null(),
+    });
+
+    gl::trace::start("test.trace").unwrap();
+
+    unsafe {
+        gl::ClearColor(0.0, 0.5, 1.0, 1.0);
+
+        let mut buffer = 0;
+        gl::GenBuffers(1, &mut buffer);
+        gl::BindBuffer(gl::ARRAY_BUFFER, buffer);
+
+        let data = [1.0f32, 2.0, 3.0];
+        gl::BufferData(gl::ARRAY_BUFFER, 12, data.as_ptr() as *const c_void, gl::STATIC_DRAW);
+        gl::DeleteBuffers(1, &buffer);
+    }
+
+    gl::trace::stop().unwrap();
+}